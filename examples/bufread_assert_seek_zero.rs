@@ -1,8 +1,10 @@
-use io_wrapper_statistics::{IOStatWrapper, IopInfoPair};
+use io_wrapper_statistics::{IOStatWrapper, IopInfoPair, StatsTable};
 
 use std::fs::File;
 use std::io::{Read, Seek, SeekFrom, BufReader};
 
+// Seeking by zero is the point of this example
+#[allow(clippy::seek_from_current)]
 fn main() {
     let file_obj = File::open("Cargo.toml").unwrap();
     let mut instrumented_raw_file = IOStatWrapper::<_, Vec<IopInfoPair>>::new(file_obj, 0);
@@ -24,12 +26,8 @@ fn main() {
     println!("XOR of all bytes in Cargo.toml is {:#x}", xor_result);
 
     // Demonstrate how BufReader reduces the number of read calls
-    println!("Buffered read was called successfully {} times",
-        instrumented_buf_file.read_call_counter().success_ctr());
-    println!("Buffered seek was called successfully {} times",
-        instrumented_buf_file.seek_call_counter().success_ctr());
-    println!("Inner read was called successfully {} times",
-        instrumented_raw_file.read_call_counter().success_ctr());
-    println!("Inner seek was called successfully {} times",
-        instrumented_raw_file.seek_call_counter().success_ctr());
+    let table = StatsTable::new()
+        .with_column("buffered", instrumented_buf_file.stats_snapshot())
+        .with_column("inner", instrumented_raw_file.stats_snapshot());
+    print!("{}", table);
 }
\ No newline at end of file
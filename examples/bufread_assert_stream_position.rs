@@ -1,4 +1,4 @@
-use io_wrapper_statistics::{IOStatWrapper, IopInfoPair};
+use io_wrapper_statistics::{IOStatWrapper, IopInfoPair, StatsTable};
 
 use std::fs::File;
 use std::io::{Read, Seek, BufReader};
@@ -24,12 +24,8 @@ fn main() {
     println!("XOR of all bytes in Cargo.toml is {:#x}", xor_result);
 
     // Demonstrate how BufReader reduces the number of read calls
-    println!("Buffered read was called successfully {} times",
-        instrumented_buf_file.read_call_counter().success_ctr());
-    println!("Buffered seek was called successfully {} times",
-        instrumented_buf_file.seek_call_counter().success_ctr());
-    println!("Inner read was called successfully {} times",
-        instrumented_raw_file.read_call_counter().success_ctr());
-    println!("Inner seek was called successfully {} times",
-        instrumented_raw_file.seek_call_counter().success_ctr());
+    let table = StatsTable::new()
+        .with_column("buffered", instrumented_buf_file.stats_snapshot())
+        .with_column("inner", instrumented_raw_file.stats_snapshot());
+    print!("{}", table);
 }
\ No newline at end of file
@@ -1,12 +1,16 @@
-use io_wrapper_statistics::{IOStatWrapper, IopInfoPair};
+use io_wrapper_statistics::{IOStatWrapper, IopInfoPair, StatsTable};
 
 use std::fs::File;
 use std::io::{Read, BufReader};
 
+// The wrapper reads from a BufReader, so reading it byte by byte is cheap
+#[allow(clippy::unbuffered_bytes)]
 fn main() {
     let file_obj = File::open("Cargo.toml").unwrap();
     let mut instrumented_raw_file = IOStatWrapper::<_, Vec<IopInfoPair>>::new(file_obj, 0);
-    let buffered_io = BufReader::new(&mut instrumented_raw_file);
+    // Keep a handle to the statistics, as the wrapper is moved into the BufReader
+    let raw_file_stats = instrumented_raw_file.shared_stats();
+    let buffered_io = BufReader::new(instrumented_raw_file);
     let mut instrumented_buf_file = IOStatWrapper::<_, Vec<IopInfoPair>>::new(buffered_io, 0);
 
     // Do something so that the loop doesn't get optimized out
@@ -17,8 +21,8 @@ fn main() {
     println!("XOR of all bytes in Cargo.toml is {:#x}", xor_result);
 
     // Demonstrate how BufReader reduces the number of read calls
-    println!("Buffered read was called successfully {} times",
-        instrumented_buf_file.read_call_counter().success_ctr());
-    println!("Inner read was called successfully {} times",
-        instrumented_raw_file.read_call_counter().success_ctr());
-}
\ No newline at end of file
+    let table = StatsTable::new()
+        .with_column("buffered", instrumented_buf_file.stats_snapshot())
+        .with_column("inner", raw_file_stats.snapshot());
+    print!("{}", table);
+}
@@ -0,0 +1,503 @@
+//! A compact binary format for I/O operation logs, with a streaming writer and an iterating reader.
+//!
+//! A trace starts with the magic bytes `IOWSTRC\0`, followed by the format version, a flags byte (bit 0 set if timestamps are stored), the starting seek position, and a length-prefixed UTF-8 label.
+//! Every operation is then stored as a tag byte holding its [`IopKind`] and which optional fields follow, its parameters and result, and its optional position, timing, and payload.
+//! Integers are stored as LEB128 varints (signed integers are zigzag encoded first), positions are stored as differences from the position after the previous operation, and start times as differences from the start of the previous operation, so that typical operations take a few bytes.
+//! Error kinds are stored as indices into a table of every stable kind, and unstable kinds as [`ErrorKind::Other`].
+
+use crate::{IopActions, IopCollector, IopKind, IopRecord, IopResults, IopTiming, error_kind_names};
+
+use std::convert::TryFrom;
+use std::io::{Error, ErrorKind, Read, SeekFrom, Write};
+use std::io::Result as IOResult;
+use std::time::Duration;
+
+const MAGIC: &[u8; 8] = b"IOWSTRC\0";
+/// The version of the binary trace format written by this crate.
+pub const BINARY_TRACE_VERSION: u32 = 1;
+
+const HEADER_TIMESTAMPS: u8 = 0x01;
+
+// Tag byte layout: the low 4 bits hold the operation kind
+const TAG_KIND_MASK: u8 = 0x0f;
+const TAG_ERROR: u8 = 0x10;
+const TAG_POSITION: u8 = 0x20;
+const TAG_TIMING: u8 = 0x40;
+const TAG_EXTRA: u8 = 0x80;
+// Extra flags byte, only present when TAG_EXTRA is set
+const EXTRA_INJECTED: u8 = 0x01;
+const EXTRA_PAYLOAD: u8 = 0x02;
+const EXTRA_PAYLOAD_HASH: u8 = 0x04;
+const EXTRA_PAYLOAD_REDACTED: u8 = 0x08;
+const EXTRA_PAYLOAD_TRUNCATED: u8 = 0x10;
+
+const KINDS: [IopKind; 11] = [
+    IopKind::Read, IopKind::ReadVectored, IopKind::Seek, IopKind::Write,
+    IopKind::WriteVectored, IopKind::Flush, IopKind::FillBuf, IopKind::Consume,
+    IopKind::ReadUntil, IopKind::ReadLine, IopKind::Shutdown
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The header of a binary trace, describing the wrapper the operations were recorded from.
+pub struct BinaryTraceHeader {
+    version: u32,
+    start_seek_pos: u64,
+    timestamps: bool,
+    label: String
+}
+impl BinaryTraceHeader {
+    /// Create a header for a wrapper with the given starting seek position, without timestamps or a label.
+    pub fn new(start_seek_pos: u64) -> BinaryTraceHeader {
+        BinaryTraceHeader {
+            version: BINARY_TRACE_VERSION,
+            start_seek_pos,
+            timestamps: false,
+            label: String::new()
+        }
+    }
+    /// Set whether to store operation timing, which is otherwise dropped to keep the trace smaller.
+    pub fn with_timestamps(mut self, timestamps: bool) -> BinaryTraceHeader {
+        self.timestamps = timestamps;
+        self
+    }
+    /// Set a free-form label describing the traced I/O object.
+    pub fn with_label<S: Into<String>>(mut self, label: S) -> BinaryTraceHeader {
+        self.label = label.into();
+        self
+    }
+    /// Returns the format version of the trace.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+    pub fn start_seek_pos(&self) -> u64 {
+        self.start_seek_pos
+    }
+    pub fn timestamps(&self) -> bool {
+        self.timestamps
+    }
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+/// Running state used to delta-encode positions and start times.
+#[derive(Debug, Clone, Copy)]
+struct DeltaState {
+    position: u64,
+    start_ns: u64
+}
+impl DeltaState {
+    fn new(header: &BinaryTraceHeader) -> DeltaState {
+        DeltaState { position: header.start_seek_pos, start_ns: 0 }
+    }
+}
+
+#[derive(Debug)]
+/// A log collector that writes every operation to a [`Write`] sink in the binary trace format as soon as it is logged.
+///
+/// The header is written along with the first operation, or by [`BinaryTraceWriter::finish()`] if there were none.
+/// Like [`crate::TraceWriter`], the first write error is stored (see [`BinaryTraceWriter::error()`]) and later operations are discarded.
+/// Each tag byte and varint is a separate write of a few bytes, so the sink should be buffered like that of [`crate::TraceWriter::new()`]; only [`BinaryTraceWriter::finish()`] flushes it.
+pub struct BinaryTraceWriter<W: Write> {
+    sink: W,
+    header: BinaryTraceHeader,
+    header_written: bool,
+    delta: DeltaState,
+    written_count: u64,
+    error: Option<Error>
+}
+impl<W: Write> BinaryTraceWriter<W> {
+    pub fn new(sink: W, header: BinaryTraceHeader) -> BinaryTraceWriter<W> {
+        BinaryTraceWriter {
+            sink,
+            delta: DeltaState::new(&header),
+            header,
+            header_written: false,
+            written_count: 0,
+            error: None
+        }
+    }
+    pub fn header(&self) -> &BinaryTraceHeader {
+        &self.header
+    }
+    /// Returns the number of operations written so far.
+    pub fn written_count(&self) -> u64 {
+        self.written_count
+    }
+    /// Returns the first error that occurred while writing the trace, if any.
+    pub fn error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+    /// Write the header if it has not been written yet, flush the sink, and return it, or return the first error that occurred while writing the trace.
+    pub fn finish(mut self) -> IOResult<W> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        self.write_header()?;
+        self.sink.flush()?;
+        Ok(self.sink)
+    }
+
+    fn write_header(&mut self) -> IOResult<()> {
+        if self.header_written {
+            return Ok(());
+        }
+        self.sink.write_all(MAGIC)?;
+        write_varint(&mut self.sink, u64::from(self.header.version))?;
+        let flags = if self.header.timestamps { HEADER_TIMESTAMPS } else { 0 };
+        self.sink.write_all(&[flags])?;
+        write_varint(&mut self.sink, self.header.start_seek_pos)?;
+        write_bytes(&mut self.sink, self.header.label.as_bytes())?;
+        self.header_written = true;
+        Ok(())
+    }
+    fn write_iop(&mut self, iop_record: &IopRecord) -> IOResult<()> {
+        self.write_header()?;
+        let action = iop_record.action();
+        let result = iop_record.result();
+        let timing = iop_record.timing().filter(|_| self.header.timestamps);
+        let mut extra = 0;
+        if iop_record.is_injected() {
+            extra |= EXTRA_INJECTED;
+        }
+        if iop_record.payload().is_some() {
+            extra |= EXTRA_PAYLOAD;
+        }
+        if iop_record.payload_hash().is_some() {
+            extra |= EXTRA_PAYLOAD_HASH;
+        }
+        if iop_record.is_payload_redacted() {
+            extra |= EXTRA_PAYLOAD_REDACTED;
+        }
+        if iop_record.is_payload_truncated() {
+            extra |= EXTRA_PAYLOAD_TRUNCATED;
+        }
+        let mut tag = action.kind() as u8;
+        if !result.is_ok() {
+            tag |= TAG_ERROR;
+        }
+        if iop_record.position_before().is_some() {
+            tag |= TAG_POSITION;
+        }
+        if timing.is_some() {
+            tag |= TAG_TIMING;
+        }
+        if extra != 0 {
+            tag |= TAG_EXTRA;
+        }
+        self.sink.write_all(&[tag])?;
+        if extra != 0 {
+            self.sink.write_all(&[extra])?;
+        }
+
+        match *action {
+            IopActions::Read(n) | IopActions::Write(n) | IopActions::Consume(n)
+                => write_varint(&mut self.sink, n as u64)?,
+            IopActions::ReadVectored(count, n) => {
+                write_varint(&mut self.sink, count as u64)?;
+                write_varint(&mut self.sink, n as u64)?;
+            },
+            IopActions::WriteVectored(ref buf_lens) => {
+                write_varint(&mut self.sink, buf_lens.len() as u64)?;
+                for &buf_len in buf_lens {
+                    write_varint(&mut self.sink, buf_len as u64)?;
+                }
+            },
+            IopActions::Seek(pos) => {
+                let (whence, offset) = match pos {
+                    SeekFrom::Start(n) => (0, n),
+                    SeekFrom::End(n) => (1, zigzag(n)),
+                    SeekFrom::Current(n) => (2, zigzag(n))
+                };
+                self.sink.write_all(&[whence])?;
+                write_varint(&mut self.sink, offset)?;
+            },
+            IopActions::ReadUntil(byte) => self.sink.write_all(&[byte])?,
+            IopActions::Flush | IopActions::FillBuf | IopActions::ReadLine
+                | IopActions::Shutdown => {}
+        }
+        match (result.error_kind(), result) {
+            (Some(error_kind), _) => {
+                let index = error_kind_names()
+                    .position(|(kind, _)| *kind == error_kind)
+                    .or_else(|| error_kind_names().position(|(kind, _)| *kind == ErrorKind::Other))
+                    .unwrap();
+                write_varint(&mut self.sink, index as u64)?;
+            },
+            (None, IopResults::Seek(Ok(n))) => write_varint(&mut self.sink, n)?,
+            (None, result) => if let Some(n) = result.returned_len() {
+                write_varint(&mut self.sink, n as u64)?;
+            }
+        }
+
+        if let (Some(before), Some(after)) = (iop_record.position_before(), iop_record.position_after()) {
+            write_varint(&mut self.sink, zigzag(before.wrapping_sub(self.delta.position) as i64))?;
+            write_varint(&mut self.sink, zigzag(after.wrapping_sub(before) as i64))?;
+            self.delta.position = after;
+        }
+        if let Some(timing) = timing {
+            let start_ns = nanos(timing.start());
+            write_varint(&mut self.sink, zigzag(start_ns.wrapping_sub(self.delta.start_ns) as i64))?;
+            write_varint(&mut self.sink, nanos(timing.elapsed()))?;
+            self.delta.start_ns = start_ns;
+        }
+        if let Some(payload) = iop_record.payload() {
+            write_bytes(&mut self.sink, payload)?;
+        }
+        if let Some(payload_hash) = iop_record.payload_hash() {
+            self.sink.write_all(&payload_hash.to_le_bytes())?;
+        }
+        self.written_count += 1;
+        Ok(())
+    }
+}
+impl<W: Write> IopCollector for BinaryTraceWriter<W> {
+    fn collect_iop(&mut self, iop_record: IopRecord) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(error) = self.write_iop(&iop_record) {
+            self.error = Some(error);
+        }
+    }
+}
+
+#[derive(Debug)]
+/// An iterator over the operations stored in a binary trace.
+///
+/// Each item is an [`IopRecord`] as it was logged, except that timing is only present if the trace stores timestamps and unknown error kinds are [`ErrorKind::Other`].
+/// A malformed trace yields an [`ErrorKind::InvalidData`] error, and a trace that ends in the middle of an operation an [`ErrorKind::UnexpectedEof`] error, after which the iterator ends.
+pub struct BinaryTraceReader<R: Read> {
+    source: R,
+    header: BinaryTraceHeader,
+    delta: DeltaState,
+    done: bool
+}
+impl<R: Read> BinaryTraceReader<R> {
+    /// Read the header of a binary trace.
+    ///
+    /// Fails with [`ErrorKind::InvalidData`] if `source` is not a binary trace or was written by a newer version of this crate.
+    pub fn new(mut source: R) -> IOResult<BinaryTraceReader<R>> {
+        let mut magic = [0; 8];
+        source.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not a binary trace"));
+        }
+        let version = u32::try_from(read_varint(&mut source)?)
+            .map_err(|_| invalid_data("invalid trace version"))?;
+        if version > BINARY_TRACE_VERSION {
+            return Err(invalid_data("unsupported trace version"));
+        }
+        let flags = read_byte(&mut source)?;
+        let start_seek_pos = read_varint(&mut source)?;
+        let label = String::from_utf8(read_bytes(&mut source)?)
+            .map_err(|_| invalid_data("trace label is not valid UTF-8"))?;
+        let header = BinaryTraceHeader {
+            version,
+            start_seek_pos,
+            timestamps: flags & HEADER_TIMESTAMPS != 0,
+            label
+        };
+        Ok(BinaryTraceReader {
+            source,
+            delta: DeltaState::new(&header),
+            header,
+            done: false
+        })
+    }
+    pub fn header(&self) -> &BinaryTraceHeader {
+        &self.header
+    }
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.source
+    }
+
+    fn read_iop(&mut self, tag: u8) -> IOResult<IopRecord> {
+        let source = &mut self.source;
+        let kind = *KINDS.get(usize::from(tag & TAG_KIND_MASK))
+            .ok_or_else(|| invalid_data("invalid operation kind"))?;
+        let extra = if tag & TAG_EXTRA != 0 { read_byte(source)? } else { 0 };
+
+        let action = match kind {
+            IopKind::Read => IopActions::Read(read_usize(source)?),
+            IopKind::Write => IopActions::Write(read_usize(source)?),
+            IopKind::Consume => IopActions::Consume(read_usize(source)?),
+            IopKind::ReadVectored => {
+                let count = read_usize(source)?;
+                IopActions::ReadVectored(count, read_usize(source)?)
+            },
+            IopKind::WriteVectored => {
+                let count = read_usize(source)?;
+                // Not preallocated, as a corrupt count could be huge
+                IopActions::WriteVectored((0..count).map(|_| read_usize(source)).collect::<IOResult<_>>()?)
+            },
+            IopKind::Seek => {
+                let whence = read_byte(source)?;
+                let offset = read_varint(source)?;
+                IopActions::Seek(match whence {
+                    0 => SeekFrom::Start(offset),
+                    1 => SeekFrom::End(unzigzag(offset)),
+                    2 => SeekFrom::Current(unzigzag(offset)),
+                    _ => return Err(invalid_data("invalid seek origin"))
+                })
+            },
+            IopKind::ReadUntil => IopActions::ReadUntil(read_byte(source)?),
+            IopKind::Flush => IopActions::Flush,
+            IopKind::FillBuf => IopActions::FillBuf,
+            IopKind::ReadLine => IopActions::ReadLine,
+            IopKind::Shutdown => IopActions::Shutdown
+        };
+        let result = if tag & TAG_ERROR != 0 {
+            let index = read_usize(source)?;
+            let error_kind = error_kind_names().nth(index).map_or(ErrorKind::Other, |(kind, _)| *kind);
+            if kind == IopKind::Consume {
+                return Err(invalid_data("consume cannot fail"));
+            }
+            IopResults::failure(kind, error_kind)
+        } else {
+            match kind {
+                IopKind::Read => IopResults::Read(Ok(read_usize(source)?)),
+                IopKind::ReadVectored => IopResults::ReadVectored(Ok(read_usize(source)?)),
+                IopKind::Seek => IopResults::Seek(Ok(read_varint(source)?)),
+                IopKind::Write => IopResults::Write(Ok(read_usize(source)?)),
+                IopKind::WriteVectored => IopResults::WriteVectored(Ok(read_usize(source)?)),
+                IopKind::Flush => IopResults::Flush(Ok(())),
+                IopKind::FillBuf => IopResults::FillBuf(Ok(read_usize(source)?)),
+                IopKind::Consume => IopResults::Consume,
+                IopKind::ReadUntil => IopResults::ReadUntil(Ok(read_usize(source)?)),
+                IopKind::ReadLine => IopResults::ReadLine(Ok(read_usize(source)?)),
+                IopKind::Shutdown => IopResults::Shutdown(Ok(()))
+            }
+        };
+        let mut iop_record = IopRecord::new(action, result);
+
+        if tag & TAG_POSITION != 0 {
+            let before = self.delta.position.wrapping_add(unzigzag(read_varint(source)?) as u64);
+            let after = before.wrapping_add(unzigzag(read_varint(source)?) as u64);
+            iop_record = iop_record.with_position(before, after);
+            self.delta.position = after;
+        }
+        if tag & TAG_TIMING != 0 {
+            let start_ns = self.delta.start_ns.wrapping_add(unzigzag(read_varint(source)?) as u64);
+            let elapsed_ns = read_varint(source)?;
+            iop_record = iop_record.with_timing(IopTiming::new(
+                Duration::from_nanos(start_ns), Duration::from_nanos(elapsed_ns)));
+            self.delta.start_ns = start_ns;
+        }
+        if extra & EXTRA_PAYLOAD != 0 {
+            iop_record = iop_record.with_payload(read_bytes(source)?);
+        }
+        if extra & EXTRA_PAYLOAD_HASH != 0 {
+            let mut hash = [0; 8];
+            source.read_exact(&mut hash)?;
+            iop_record = iop_record.with_payload_hash(u64::from_le_bytes(hash));
+        }
+        if extra & EXTRA_PAYLOAD_REDACTED != 0 {
+            iop_record = iop_record.mark_payload_redacted();
+        }
+        if extra & EXTRA_PAYLOAD_TRUNCATED != 0 {
+            iop_record = iop_record.mark_payload_truncated();
+        }
+        if extra & EXTRA_INJECTED != 0 {
+            iop_record = iop_record.mark_injected();
+        }
+        Ok(iop_record)
+    }
+}
+impl<R: Read> Iterator for BinaryTraceReader<R> {
+    type Item = IOResult<IopRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        // End of file is only valid between operations
+        let mut tag = [0; 1];
+        let result = loop {
+            match self.source.read(&mut tag) {
+                Ok(0) => {
+                    self.done = true;
+                    return None;
+                },
+                Ok(_) => break self.read_iop(tag[0]),
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => break Err(e)
+            }
+        };
+        if result.is_err() {
+            self.done = true;
+        }
+        Some(result)
+    }
+}
+
+fn invalid_data(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+fn zigzag(n: i64) -> u64 {
+    ((n << 1) ^ (n >> 63)) as u64
+}
+fn unzigzag(n: u64) -> i64 {
+    ((n >> 1) as i64) ^ -((n & 1) as i64)
+}
+
+fn write_varint<W: Write>(sink: &mut W, mut n: u64) -> IOResult<()> {
+    let mut buf = [0; 10];
+    let mut len = 0;
+    loop {
+        let byte = (n & 0x7f) as u8;
+        n >>= 7;
+        if n == 0 {
+            buf[len] = byte;
+            len += 1;
+            break;
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+    sink.write_all(&buf[..len])
+}
+fn write_bytes<W: Write>(sink: &mut W, bytes: &[u8]) -> IOResult<()> {
+    write_varint(sink, bytes.len() as u64)?;
+    sink.write_all(bytes)
+}
+
+fn read_byte<R: Read>(source: &mut R) -> IOResult<u8> {
+    let mut byte = [0; 1];
+    source.read_exact(&mut byte)?;
+    Ok(byte[0])
+}
+fn read_varint<R: Read>(source: &mut R) -> IOResult<u64> {
+    let mut n = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = read_byte(source)?;
+        let bits = u64::from(byte & 0x7f);
+        // The tenth byte may only hold the top bit
+        if shift == 63 && bits > 1 {
+            return Err(invalid_data("varint is too long"));
+        }
+        n |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Ok(n);
+        }
+    }
+    Err(invalid_data("varint is too long"))
+}
+fn read_usize<R: Read>(source: &mut R) -> IOResult<usize> {
+    usize::try_from(read_varint(source)?)
+        .map_err(|_| invalid_data("length does not fit in usize"))
+}
+fn read_bytes<R: Read>(source: &mut R) -> IOResult<Vec<u8>> {
+    let len = read_usize(source)?;
+    // Read incrementally so that a corrupt length cannot allocate too much
+    let mut bytes = Vec::new();
+    (&mut *source).take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len {
+        return Err(ErrorKind::UnexpectedEof.into());
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,143 @@
+//! Export of timed I/O operation logs as Chrome Trace Event JSON, for viewing in Perfetto or `chrome://tracing`.
+
+use crate::IopRecord;
+
+use std::convert::TryFrom;
+use std::io::Write;
+use std::io::Result as IOResult;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Default)]
+/// Converts the operation logs of one or more [`crate::IOStatWrapper`]s into a Chrome Trace Event JSON file.
+///
+/// Each log becomes a track (a thread of a single process) named after the wrapper, with one complete (`"ph": "X"`) event per operation.
+/// Events are named after the [`crate::IopKind`] and carry the requested and returned sizes, the seek positions, the error kind of failures, and whether the failure was injected in their `args`.
+///
+/// Only operations with timing information are exported, so timing must have been enabled on the wrappers.
+/// Give every wrapper the same epoch with [`crate::IOStatWrapper::enable_timing_with_epoch()`] to put their tracks on the same timeline.
+pub struct ChromeTraceExporter {
+    process_name: Option<String>,
+    track_names: Vec<String>,
+    // Rendered JSON objects of every event
+    events: Vec<String>,
+    skipped_count: usize
+}
+impl ChromeTraceExporter {
+    pub fn new() -> ChromeTraceExporter {
+        ChromeTraceExporter::default()
+    }
+    /// Name the process that holds every track.
+    pub fn with_process_name<S: Into<String>>(mut self, process_name: S) -> ChromeTraceExporter {
+        self.process_name = Some(process_name.into());
+        self
+    }
+    /// Add a track with the given name holding the timed operations of `iop_log`.
+    pub fn with_track<'a, S, I>(mut self, track_name: S, iop_log: I) -> ChromeTraceExporter
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = &'a IopRecord>
+    {
+        self.track_names.push(track_name.into());
+        let tid = self.track_names.len();
+        for iop_record in iop_log {
+            match iop_record.timing() {
+                Some(timing) => {
+                    let event = render_event(iop_record, tid, timing.start(), timing.elapsed());
+                    self.events.push(event);
+                },
+                None => self.skipped_count += 1
+            }
+        }
+        self
+    }
+    /// Returns the number of tracks.
+    pub fn track_count(&self) -> usize {
+        self.track_names.len()
+    }
+    /// Returns the number of exported operations.
+    pub fn event_count(&self) -> usize {
+        self.events.len()
+    }
+    /// Returns the number of operations that were not exported because they have no timing information.
+    pub fn skipped_count(&self) -> usize {
+        self.skipped_count
+    }
+    /// Write the trace as a JSON object to the given sink.
+    pub fn write_json<W: Write>(&self, mut sink: W) -> IOResult<()> {
+        sink.write_all(self.to_json().as_bytes())?;
+        sink.flush()
+    }
+    /// Returns the trace as a JSON object.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\"traceEvents\":[");
+        let mut metadata = Vec::new();
+        if let Some(process_name) = &self.process_name {
+            metadata.push(format!(
+                "{{\"name\":\"process_name\",\"ph\":\"M\",\"pid\":1,\"tid\":0,\"args\":{{\"name\":{}}}}}",
+                json_string(process_name)));
+        }
+        for (i, track_name) in self.track_names.iter().enumerate() {
+            metadata.push(format!(
+                "{{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":1,\"tid\":{},\"args\":{{\"name\":{}}}}}",
+                i + 1, json_string(track_name)));
+        }
+        for (i, event) in metadata.iter().chain(self.events.iter()).enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            json.push('\n');
+            json.push_str(event);
+        }
+        json.push_str("\n],\"displayTimeUnit\":\"ns\"}\n");
+        json
+    }
+}
+
+fn render_event(iop_record: &IopRecord, tid: usize, start: Duration, elapsed: Duration) -> String {
+    let result = iop_record.result();
+    let mut args = Vec::new();
+    if let Some(requested) = iop_record.action().requested_len() {
+        args.push(format!("\"requested\":{}", requested));
+    }
+    if let Some(returned) = result.returned_len() {
+        args.push(format!("\"returned\":{}", returned));
+    }
+    if let Some(position_before) = iop_record.position_before() {
+        args.push(format!("\"pos_before\":{}", position_before));
+    }
+    if let Some(position_after) = iop_record.position_after() {
+        args.push(format!("\"pos_after\":{}", position_after));
+    }
+    if let Some(error_kind) = result.error_kind() {
+        args.push(format!("\"error_kind\":\"{:?}\"", error_kind));
+    }
+    if iop_record.is_injected() {
+        args.push("\"injected\":true".to_owned());
+    }
+    format!("{{\"name\":\"{:?}\",\"cat\":\"io\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":1,\"tid\":{},\"args\":{{{}}}}}",
+        iop_record.action().kind(), micros(start), micros(elapsed), tid, args.join(","))
+}
+
+/// Format a duration in microseconds, the unit of Chrome trace timestamps, keeping nanosecond precision.
+fn micros(duration: Duration) -> String {
+    let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+    format!("{}.{:03}", nanos / 1000, nanos % 1000)
+}
+
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if u32::from(c) < 0x20 => json.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => json.push(c)
+        }
+    }
+    json.push('"');
+    json
+}
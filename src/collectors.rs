@@ -0,0 +1,199 @@
+//! Ready-made log collectors for use as the `C` parameter of [`crate::IOStatWrapper`].
+
+use crate::{IopCollector, IopInfoPair, IopKind, IopRecord, SuccessFailureCounter};
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::collections::vec_deque::Iter as VecDequeIter;
+use std::convert::TryFrom;
+use std::io::ErrorKind;
+use std::iter::Extend;
+use std::ops::Deref;
+use std::slice::Iter as SliceIter;
+use std::vec::IntoIter as VecIntoIter;
+
+#[derive(Debug, Clone, Default)]
+/// A log collector that keeps only the last `N` operations.
+///
+/// Older operations are discarded as new ones arrive, and the number of discarded operations is counted, so memory use stays bounded for long-running I/O objects.
+pub struct RingLog<const N: usize> {
+    entries: VecDeque<IopInfoPair>,
+    dropped_count: u64
+}
+impl<const N: usize> RingLog<N> {
+    /// Returns the maximum number of operations kept in the log.
+    pub fn capacity(&self) -> usize {
+        N
+    }
+    /// Returns the number of operations currently kept in the log.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    /// Returns the number of operations that were discarded to stay within capacity.
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped_count
+    }
+    /// Returns the total number of operations that were added to the log.
+    pub fn total_count(&self) -> u64 {
+        self.dropped_count + self.entries.len() as u64
+    }
+    /// Returns the kept operation at the given index, where index 0 is the oldest kept operation.
+    pub fn get(&self, index: usize) -> Option<&IopInfoPair> {
+        self.entries.get(index)
+    }
+    /// Iterate over the kept operations from oldest to newest.
+    pub fn iter(&self) -> VecDequeIter<'_, IopInfoPair> {
+        self.entries.iter()
+    }
+    /// Remove all kept operations and reset the dropped operation count.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.dropped_count = 0;
+    }
+}
+impl<const N: usize> Extend<IopInfoPair> for RingLog<N> {
+    fn extend<I: IntoIterator<Item = IopInfoPair>>(&mut self, iter: I) {
+        for iop_info in iter {
+            if N == 0 {
+                self.dropped_count += 1;
+                continue;
+            }
+            if self.entries.len() == N {
+                self.entries.pop_front();
+                self.dropped_count += 1;
+            }
+            self.entries.push_back(iop_info);
+        }
+    }
+}
+impl<'a, const N: usize> IntoIterator for &'a RingLog<N> {
+    type Item = &'a IopInfoPair;
+    type IntoIter = VecDequeIter<'a, IopInfoPair>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// A log collector that keeps every operation as an [`IopRecord`], along with its timing, payload, and positions.
+///
+/// A `Vec<IopInfoPair>` collector only keeps the operations and their results.
+pub struct RecordLog {
+    records: Vec<IopRecord>
+}
+impl RecordLog {
+    pub fn new() -> RecordLog {
+        RecordLog::default()
+    }
+    /// Returns the kept operations, from oldest to newest.
+    pub fn into_vec(self) -> Vec<IopRecord> {
+        self.records
+    }
+}
+impl Deref for RecordLog {
+    type Target = [IopRecord];
+    fn deref(&self) -> &[IopRecord] {
+        &self.records
+    }
+}
+impl IopCollector for RecordLog {
+    fn collect_iop(&mut self, iop_record: IopRecord) {
+        self.records.push(iop_record);
+    }
+}
+impl IntoIterator for RecordLog {
+    type Item = IopRecord;
+    type IntoIter = VecIntoIter<IopRecord>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.records.into_iter()
+    }
+}
+impl<'a> IntoIterator for &'a RecordLog {
+    type Item = &'a IopRecord;
+    type IntoIter = SliceIter<'a, IopRecord>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.records.iter()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// A log collector that discards every operation.
+///
+/// Use this when only the counters of the wrapper are needed.
+/// The wrapper does not build records or capture payloads for it, even if payload capture is enabled.
+pub struct NoLog;
+impl IopCollector for NoLog {
+    const DISCARDS_ALL: bool = true;
+    #[inline]
+    fn collect_iop(&mut self, _iop_record: IopRecord) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// Aggregated statistics for a single kind of operation.
+pub struct OpKindSummary {
+    call_counter: SuccessFailureCounter<u64>,
+    returned_byte_counter: u64,
+    failure_counters: HashMap<ErrorKind, u64>
+}
+impl OpKindSummary {
+    /// Returns the number of operations of this kind.
+    pub fn call_counter(&self) -> &SuccessFailureCounter<u64> {
+        &self.call_counter
+    }
+    /// Returns the sum of [`crate::IopResults::returned_len()`] over successful operations of this kind.
+    pub fn returned_byte_counter(&self) -> u64 {
+        self.returned_byte_counter
+    }
+    /// Returns the number of operations of this kind that failed with the given error kind.
+    pub fn failure_counter(&self, error_kind: ErrorKind) -> u64 {
+        self.failure_counters.get(&error_kind).copied().unwrap_or(0)
+    }
+    /// Iterate over the error kinds that operations of this kind failed with, along with their counts.
+    pub fn failure_counters(&self) -> impl Iterator<Item = (ErrorKind, u64)> + '_ {
+        self.failure_counters.iter().map(|(error_kind, ctr)| (*error_kind, *ctr))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// A log collector that folds every operation into per-kind counts and per-[`ErrorKind`] failure breakdowns, without storing the operations themselves.
+pub struct OpSummary {
+    summaries: BTreeMap<IopKind, OpKindSummary>
+}
+impl OpSummary {
+    /// Returns the summary for the given kind of operation, or `None` if no such operation was recorded.
+    pub fn get(&self, kind: IopKind) -> Option<&OpKindSummary> {
+        self.summaries.get(&kind)
+    }
+    /// Iterate over the summaries of every recorded kind of operation.
+    pub fn iter(&self) -> impl Iterator<Item = (IopKind, &OpKindSummary)> {
+        self.summaries.iter().map(|(kind, summary)| (*kind, summary))
+    }
+    /// Returns the number of failures with the given error kind across all kinds of operations.
+    pub fn failure_counter(&self, error_kind: ErrorKind) -> u64 {
+        self.summaries.values()
+            .map(|summary| summary.failure_counter(error_kind))
+            .sum()
+    }
+}
+impl Extend<IopInfoPair> for OpSummary {
+    fn extend<I: IntoIterator<Item = IopInfoPair>>(&mut self, iter: I) {
+        for (action, result) in iter {
+            let summary = self.summaries.entry(action.kind())
+                .or_default();
+            match result.error_kind() {
+                None => {
+                    summary.call_counter.increment_success();
+                    if let Some(n) = result.returned_len() {
+                        summary.returned_byte_counter += u64::try_from(n).unwrap();
+                    }
+                },
+                Some(error_kind) => {
+                    summary.call_counter.increment_failure();
+                    *summary.failure_counters.entry(error_kind).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+}
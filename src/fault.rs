@@ -0,0 +1,199 @@
+//! Injection of I/O failures and short transfers according to a programmable schedule.
+
+use crate::IopKind;
+
+use std::io::ErrorKind;
+
+#[derive(Debug, Clone)]
+/// A small, seedable pseudorandom number generator (SplitMix64), so that randomized schedules are reproducible.
+pub(crate) struct SplitMix64 {
+    state: u64
+}
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    /// Returns a uniformly distributed value in `0.0..1.0`.
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// The condition under which a [`FaultRule`] injects a failure.
+pub enum FaultTrigger {
+    /// Fail the given call (counting from 1) of the rule's kind of operation, and no other.
+    NthCall(u64),
+    /// Fail every operation of the rule's kind that would reach the byte at the given offset, like a bad sector.
+    ///
+    /// Operations with a requested size (see [`crate::IopActions::requested_len()`]) reach the offset when it lies within `seek_pos..seek_pos + requested_len`.
+    /// Delimited reads and buffer fills may read any amount, so they reach the offset when they start at or before it.
+    /// Seeks and flushes do not transfer data, so they fail once they start at or after the offset.
+    ByteOffset(u64),
+    /// Fail each operation of the rule's kind with the given probability, using the random number generator of the [`FaultSchedule`].
+    Probability(f64)
+}
+
+#[derive(Debug, Clone)]
+/// A rule that injects failures of one kind of operation.
+pub struct FaultRule {
+    kind: IopKind,
+    trigger: FaultTrigger,
+    error_kind: ErrorKind,
+    call_counter: u64
+}
+impl FaultRule {
+    /// Create a rule that fails operations of the given kind with the given [`ErrorKind`] whenever `trigger` fires.
+    ///
+    /// # Panics
+    /// Panics if `trigger` is a [`FaultTrigger::Probability`] that is not within `0.0..=1.0`.
+    pub fn new(kind: IopKind, trigger: FaultTrigger, error_kind: ErrorKind) -> FaultRule {
+        if let FaultTrigger::Probability(probability) = trigger {
+            assert!((0.0..=1.0).contains(&probability),
+                "probability {} is not within 0.0..=1.0", probability);
+        }
+        FaultRule { kind, trigger, error_kind, call_counter: 0 }
+    }
+    /// Create a rule that fails operations of the given kind with [`ErrorKind::Interrupted`], which callers are required to retry.
+    pub fn interrupted(kind: IopKind, trigger: FaultTrigger) -> FaultRule {
+        FaultRule::new(kind, trigger, ErrorKind::Interrupted)
+    }
+    /// Create a rule that fails operations of the given kind with [`ErrorKind::WouldBlock`], as a nonblocking I/O object that is not ready would.
+    pub fn would_block(kind: IopKind, trigger: FaultTrigger) -> FaultRule {
+        FaultRule::new(kind, trigger, ErrorKind::WouldBlock)
+    }
+    pub fn kind(&self) -> IopKind {
+        self.kind
+    }
+    pub fn trigger(&self) -> FaultTrigger {
+        self.trigger
+    }
+    pub fn error_kind(&self) -> ErrorKind {
+        self.error_kind
+    }
+}
+
+#[derive(Debug, Clone)]
+/// A set of [`FaultRule`]s to apply to an [`crate::IOStatWrapper`] with [`crate::IOStatWrapper::set_fault_schedule()`].
+///
+/// Every rule sees every operation of its kind, so the outcome only depends on the sequence of operations and the seed.
+/// If several rules fire for the same operation, the first one added decides the [`ErrorKind`].
+/// Injected failures do not reach the inner I/O object, are counted as failures in the wrapper counters, and are flagged with [`crate::IopRecord::is_injected()`] in the log.
+///
+/// Faults are injected into the synchronous [`std::io`] trait methods; [`IopKind::Consume`] cannot fail and is never injected.
+pub struct FaultSchedule {
+    rules: Vec<FaultRule>,
+    rng: SplitMix64,
+    injected_counter: u64
+}
+impl FaultSchedule {
+    /// Create an empty schedule whose [`FaultTrigger::Probability`] rules use a random number generator seeded with `seed`.
+    pub fn new(seed: u64) -> FaultSchedule {
+        FaultSchedule {
+            rules: Vec::new(),
+            rng: SplitMix64::new(seed),
+            injected_counter: 0
+        }
+    }
+    /// Add a rule to the schedule.
+    pub fn with_rule(mut self, rule: FaultRule) -> FaultSchedule {
+        self.rules.push(rule);
+        self
+    }
+    /// Returns the rules of the schedule in the order they were added.
+    pub fn rules(&self) -> &[FaultRule] {
+        &self.rules
+    }
+    /// Returns the number of failures injected so far.
+    pub fn injected_counter(&self) -> u64 {
+        self.injected_counter
+    }
+    /// Decide whether to fail an operation of the given kind starting at `seek_pos`, returning the error kind to inject.
+    pub(crate) fn check(&mut self, kind: IopKind, seek_pos: u64, requested_len: Option<usize>) -> Option<ErrorKind> {
+        if kind == IopKind::Consume {
+            return None;
+        }
+        let mut injected = None;
+        for rule in self.rules.iter_mut().filter(|rule| rule.kind == kind) {
+            rule.call_counter += 1;
+            let fired = match rule.trigger {
+                FaultTrigger::NthCall(n) => rule.call_counter == n,
+                FaultTrigger::ByteOffset(offset) => match (kind, requested_len) {
+                    (IopKind::Seek, _) | (IopKind::Flush, _) => seek_pos >= offset,
+                    (_, Some(len)) => seek_pos <= offset
+                        && offset - seek_pos < len as u64,
+                    (_, None) => seek_pos <= offset
+                },
+                FaultTrigger::Probability(probability) => {
+                    self.rng.next_f64() < probability
+                }
+            };
+            if fired && injected.is_none() {
+                injected = Some(rule.error_kind);
+            }
+        }
+        if injected.is_some() {
+            self.injected_counter += 1;
+        }
+        injected
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// A policy for deliberately splitting reads or writes into shorter transfers, set with [`crate::IOStatWrapper::set_read_split_policy()`] and [`crate::IOStatWrapper::set_write_split_policy()`].
+///
+/// Short transfers are allowed by the [`std::io::Read`] and [`std::io::Write`] contracts, so correct code must handle them.
+/// Transfers are never shortened to zero bytes unless the buffer is empty, as that would signal end of file for reads.
+pub enum SplitPolicy {
+    /// Transfer at most one byte per call.
+    OneByte,
+    /// Transfer at most the given number of bytes per call.
+    FixedChunk(usize),
+    /// Transfer a uniformly random number of bytes between one and the buffer length, using a random number generator seeded with the given value.
+    RandomSplit(u64)
+}
+
+#[derive(Debug, Clone)]
+/// A [`SplitPolicy`] together with its random number generator state.
+pub(crate) struct Splitter {
+    policy: SplitPolicy,
+    rng: SplitMix64
+}
+impl Splitter {
+    /// # Panics
+    /// Panics if `policy` is a [`SplitPolicy::FixedChunk`] of zero bytes.
+    pub(crate) fn new(policy: SplitPolicy) -> Splitter {
+        let seed = match policy {
+            SplitPolicy::FixedChunk(chunk_len) => {
+                assert!(chunk_len > 0, "chunk length must be nonzero");
+                0
+            },
+            SplitPolicy::RandomSplit(seed) => seed,
+            SplitPolicy::OneByte => 0
+        };
+        Splitter { policy, rng: SplitMix64::new(seed) }
+    }
+    pub(crate) fn policy(&self) -> SplitPolicy {
+        self.policy
+    }
+    /// Returns how many of the `len` requested bytes to pass on to the inner I/O object.
+    pub(crate) fn limit(&mut self, len: usize) -> usize {
+        if len == 0 {
+            return 0;
+        }
+        match self.policy {
+            SplitPolicy::OneByte => 1,
+            SplitPolicy::FixedChunk(chunk_len) => chunk_len.min(len),
+            SplitPolicy::RandomSplit(_) => {
+                1 + (self.rng.next_u64() % len as u64) as usize
+            }
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! Implementations of the [`futures_io`] I/O traits for [`IOStatWrapper`].
+//!
+//! Operations are logged and counted once they complete, and every `Poll::Pending` return is counted in [`IOStatWrapper::pending_counter()`].
+//! When timing is enabled, the start of an operation is the start of its first poll.
+//! The inner I/O object and log collector must be [`Unpin`].
+
+use crate::{IOStatWrapper, IopActions, IopResults, IopCollector, IopKind};
+
+use std::io::{IoSlice, IoSliceMut, SeekFrom};
+use std::io::Result as IOResult;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_io::{AsyncBufRead, AsyncRead, AsyncSeek, AsyncWrite};
+
+impl<T, C> AsyncRead for IOStatWrapper<T, C>
+where
+    T: AsyncRead + Unpin,
+    C: IopCollector + Unpin
+{
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<IOResult<usize>> {
+        //! Passthrough for the `inner_io` poll_read call that increments a call counter and appends a [`IopResults::Read`] object to the log once the read completes.
+        let this = self.get_mut();
+        let start = this.state.start_iop();
+        let read_poll = Pin::new(&mut this.inner_io).poll_read(cx, buf);
+        let (iop_result, payload) = match read_poll {
+            Poll::Pending => {
+                this.state.note_pending(IopKind::Read, start);
+                return Poll::Pending;
+            },
+            Poll::Ready(Ok(n)) => {
+                this.state.read_call_counter.increment_success();
+                this.state.advance_read(n);
+                (IopResults::Read(Ok(n)), this.state.capture_payload([&buf[..n]], n))
+            },
+            Poll::Ready(Err(ref e)) => {
+                this.state.read_call_counter.increment_failure();
+                (IopResults::Read(Err(e.kind())), None)
+            }
+        };
+        let start = this.state.take_async_start(IopKind::Read, start);
+        this.state.log_iop_payload(IopActions::Read(buf.len()), iop_result, payload, start);
+        read_poll
+    }
+    fn poll_read_vectored(self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &mut [IoSliceMut<'_>]) -> Poll<IOResult<usize>> {
+        //! Passthrough for the `inner_io` poll_read_vectored call that increments the read call counter and appends a [`IopResults::ReadVectored`] object to the log once the read completes.
+        let this = self.get_mut();
+        let buf_count = bufs.len();
+        let total_len = bufs.iter().map(|buf| buf.len()).sum();
+        let start = this.state.start_iop();
+        let read_poll = Pin::new(&mut this.inner_io).poll_read_vectored(cx, bufs);
+        let (iop_result, payload) = match read_poll {
+            Poll::Pending => {
+                this.state.note_pending(IopKind::ReadVectored, start);
+                return Poll::Pending;
+            },
+            Poll::Ready(Ok(n)) => {
+                this.state.read_call_counter.increment_success();
+                this.state.advance_read(n);
+                (IopResults::ReadVectored(Ok(n)),
+                    this.state.capture_payload(bufs.iter().map(|buf| &**buf), n))
+            },
+            Poll::Ready(Err(ref e)) => {
+                this.state.read_call_counter.increment_failure();
+                (IopResults::ReadVectored(Err(e.kind())), None)
+            }
+        };
+        let start = this.state.take_async_start(IopKind::ReadVectored, start);
+        this.state.log_iop_payload(IopActions::ReadVectored(buf_count, total_len),
+            iop_result, payload, start);
+        read_poll
+    }
+}
+
+impl<T, C> AsyncBufRead for IOStatWrapper<T, C>
+where
+    T: AsyncBufRead + Unpin,
+    C: IopCollector + Unpin
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IOResult<&[u8]>> {
+        //! Passthrough for the `inner_io` poll_fill_buf call that increments a call counter, counts a buffer hit or miss, and appends a [`IopResults::FillBuf`] object to the log once the call completes.
+        let this = self.get_mut();
+        let start = this.state.start_iop();
+        match Pin::new(&mut this.inner_io).poll_fill_buf(cx) {
+            Poll::Pending => {
+                this.state.note_pending(IopKind::FillBuf, start);
+                Poll::Pending
+            },
+            Poll::Ready(Ok(buf)) => {
+                let start = this.state.take_async_start(IopKind::FillBuf, start);
+                this.state.record_fill_buf(Ok(buf), start);
+                Poll::Ready(Ok(buf))
+            },
+            Poll::Ready(Err(e)) => {
+                let start = this.state.take_async_start(IopKind::FillBuf, start);
+                this.state.record_fill_buf(Err(e.kind()), start);
+                Poll::Ready(Err(e))
+            }
+        }
+    }
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        //! Passthrough for the `inner_io` consume call that advances the byte counter and seek position and appends a [`IopResults::Consume`] object to the log.
+        let this = self.get_mut();
+        let start = this.state.start_iop();
+        Pin::new(&mut this.inner_io).consume(amt);
+        this.state.record_consume(amt, start);
+    }
+}
+
+impl<T, C> AsyncSeek for IOStatWrapper<T, C>
+where
+    T: AsyncSeek + Unpin,
+    C: IopCollector + Unpin
+{
+    fn poll_seek(self: Pin<&mut Self>, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<IOResult<u64>> {
+        //! Passthrough for the `inner_io` poll_seek call that increments a call counter and appends a [`IopResults::Seek`] object to the log once the seek completes.
+        let this = self.get_mut();
+        let start = this.state.start_iop();
+        let seek_poll = Pin::new(&mut this.inner_io).poll_seek(cx, pos);
+        let iop_result = match seek_poll {
+            Poll::Pending => {
+                this.state.note_pending(IopKind::Seek, start);
+                return Poll::Pending;
+            },
+            Poll::Ready(Ok(n)) => {
+                this.state.seek_call_counter.increment_success();
+                this.state.seek_pos = n;
+                this.state.fill_buf_remaining = 0;
+                IopResults::Seek(Ok(n))
+            },
+            Poll::Ready(Err(ref e)) => {
+                this.state.seek_call_counter.increment_failure();
+                IopResults::Seek(Err(e.kind()))
+            }
+        };
+        let start = this.state.take_async_start(IopKind::Seek, start);
+        this.state.log_iop(IopActions::Seek(pos), iop_result, start);
+        seek_poll
+    }
+}
+
+impl<T, C> AsyncWrite for IOStatWrapper<T, C>
+where
+    T: AsyncWrite + Unpin,
+    C: IopCollector + Unpin
+{
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<IOResult<usize>> {
+        //! Passthrough for the `inner_io` poll_write call that increments a call counter and appends a [`IopResults::Write`] object to the log once the write completes.
+        let this = self.get_mut();
+        let start = this.state.start_iop();
+        let write_poll = Pin::new(&mut this.inner_io).poll_write(cx, buf);
+        let (iop_result, payload) = match write_poll {
+            Poll::Pending => {
+                this.state.note_pending(IopKind::Write, start);
+                return Poll::Pending;
+            },
+            Poll::Ready(Ok(n)) => {
+                this.state.write_call_counter.increment_success();
+                this.state.advance_write(n);
+                (IopResults::Write(Ok(n)), this.state.capture_payload([&buf[..n]], n))
+            },
+            Poll::Ready(Err(ref e)) => {
+                this.state.write_call_counter.increment_failure();
+                (IopResults::Write(Err(e.kind())), None)
+            }
+        };
+        let start = this.state.take_async_start(IopKind::Write, start);
+        this.state.log_iop_payload(IopActions::Write(buf.len()), iop_result, payload, start);
+        write_poll
+    }
+    fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &[IoSlice<'_>]) -> Poll<IOResult<usize>> {
+        //! Passthrough for the `inner_io` poll_write_vectored call that increments the write call counter and appends a [`IopResults::WriteVectored`] object to the log once the write completes.
+        let this = self.get_mut();
+        let start = this.state.start_iop();
+        let write_poll = Pin::new(&mut this.inner_io).poll_write_vectored(cx, bufs);
+        let (iop_result, payload) = match write_poll {
+            Poll::Pending => {
+                this.state.note_pending(IopKind::WriteVectored, start);
+                return Poll::Pending;
+            },
+            Poll::Ready(Ok(n)) => {
+                this.state.write_call_counter.increment_success();
+                this.state.advance_write(n);
+                (IopResults::WriteVectored(Ok(n)),
+                    this.state.capture_payload(bufs.iter().map(|buf| &**buf), n))
+            },
+            Poll::Ready(Err(ref e)) => {
+                this.state.write_call_counter.increment_failure();
+                (IopResults::WriteVectored(Err(e.kind())), None)
+            }
+        };
+        let start = this.state.take_async_start(IopKind::WriteVectored, start);
+        let buf_lens = bufs.iter().map(|buf| buf.len()).collect();
+        this.state.log_iop_payload(IopActions::WriteVectored(buf_lens), iop_result, payload, start);
+        write_poll
+    }
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IOResult<()>> {
+        //! Passthrough for the `inner_io` poll_flush call that increments a call counter and appends a [`IopResults::Flush`] object to the log once the flush completes.
+        let this = self.get_mut();
+        let start = this.state.start_iop();
+        let flush_poll = Pin::new(&mut this.inner_io).poll_flush(cx);
+        let iop_result = match flush_poll {
+            Poll::Pending => {
+                this.state.note_pending(IopKind::Flush, start);
+                return Poll::Pending;
+            },
+            Poll::Ready(Ok(())) => {
+                this.state.write_flush_counter.increment_success();
+                IopResults::Flush(Ok(()))
+            },
+            Poll::Ready(Err(ref e)) => {
+                this.state.write_flush_counter.increment_failure();
+                IopResults::Flush(Err(e.kind()))
+            }
+        };
+        let start = this.state.take_async_start(IopKind::Flush, start);
+        this.state.log_iop(IopActions::Flush, iop_result, start);
+        flush_poll
+    }
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IOResult<()>> {
+        //! Passthrough for the `inner_io` poll_close call that increments the shutdown counter and appends a [`IopResults::Shutdown`] object to the log once the close completes.
+        let this = self.get_mut();
+        let start = this.state.start_iop();
+        let close_poll = Pin::new(&mut this.inner_io).poll_close(cx);
+        let iop_result = match close_poll {
+            Poll::Pending => {
+                this.state.note_pending(IopKind::Shutdown, start);
+                return Poll::Pending;
+            },
+            Poll::Ready(Ok(())) => {
+                this.state.write_shutdown_counter.increment_success();
+                IopResults::Shutdown(Ok(()))
+            },
+            Poll::Ready(Err(ref e)) => {
+                this.state.write_shutdown_counter.increment_failure();
+                IopResults::Shutdown(Err(e.kind()))
+            }
+        };
+        let start = this.state.take_async_start(IopKind::Shutdown, start);
+        this.state.log_iop(IopActions::Shutdown, iop_result, start);
+        close_poll
+    }
+}
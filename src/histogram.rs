@@ -0,0 +1,179 @@
+//! Histograms of operation sizes and latencies.
+
+use crate::{IopCollector, IopKind, IopRecord};
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+/// Number of significant bits kept for each recorded value.
+/// Values are bucketed with a relative error of at most 2^-(SIGNIFICANT_BITS-1).
+const SIGNIFICANT_BITS: u32 = 7;
+const HALF_SUB_BUCKETS: usize = 1 << (SIGNIFICANT_BITS - 1);
+
+fn bucket_index(value: u64) -> usize {
+    let msb = 63 - value.leading_zeros().min(63);
+    let shift = (msb + 1).saturating_sub(SIGNIFICANT_BITS);
+    let mantissa = usize::try_from(value >> shift).unwrap();
+    usize::try_from(shift).unwrap() * HALF_SUB_BUCKETS + mantissa
+}
+/// Returns the lowest and highest values that are stored in the given bucket.
+fn bucket_bounds(index: usize) -> (u64, u64) {
+    if index < 2 * HALF_SUB_BUCKETS {
+        (index as u64, index as u64)
+    } else {
+        let shift = index / HALF_SUB_BUCKETS - 1;
+        let mantissa = (index - shift * HALF_SUB_BUCKETS) as u64;
+        let low = mantissa << shift;
+        (low, low + ((1u64 << shift) - 1))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// A histogram with log-linear buckets, in the style of HDR histograms.
+///
+/// Small values are stored exactly, and larger values are stored with a bounded relative error (under 2%).
+/// Buckets are allocated on demand, so memory use depends on the largest recorded value.
+pub struct LogLinearHistogram {
+    buckets: Vec<u64>,
+    count: u64,
+    sum: u128,
+    min: u64,
+    max: u64
+}
+impl LogLinearHistogram {
+    pub fn new() -> LogLinearHistogram {
+        LogLinearHistogram::default()
+    }
+    /// Record a single value.
+    pub fn record(&mut self, value: u64) {
+        let index = bucket_index(value);
+        if index >= self.buckets.len() {
+            self.buckets.resize(index + 1, 0);
+        }
+        self.buckets[index] += 1;
+        if self.count == 0 || value < self.min {
+            self.min = value;
+        }
+        if value > self.max {
+            self.max = value;
+        }
+        self.count += 1;
+        self.sum += u128::from(value);
+    }
+    /// Returns the number of recorded values.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// Returns the smallest recorded value, or `None` if the histogram is empty.
+    pub fn min(&self) -> Option<u64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.min)
+        }
+    }
+    /// Returns the largest recorded value, or `None` if the histogram is empty.
+    pub fn max(&self) -> Option<u64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.max)
+        }
+    }
+    /// Returns the exact mean of the recorded values, or `None` if the histogram is empty.
+    pub fn mean(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.sum as f64 / self.count as f64)
+        }
+    }
+    /// Returns the value below which the given fraction of recorded values lie, or `None` if the histogram is empty.
+    ///
+    /// The result is the highest value of the bucket containing the quantile, clamped to the recorded minimum and maximum.
+    ///
+    /// # Panics
+    /// Panics if `quantile` is not within `0.0..=1.0`.
+    pub fn value_at_quantile(&self, quantile: f64) -> Option<u64> {
+        assert!((0.0..=1.0).contains(&quantile),
+            "quantile {} is not within 0.0..=1.0", quantile);
+        if self.count == 0 {
+            return None;
+        }
+        let target = ((quantile * self.count as f64).ceil() as u64).max(1);
+        let mut seen: u64 = 0;
+        for (index, bucket_count) in self.buckets.iter().enumerate() {
+            seen += bucket_count;
+            if seen >= target {
+                let (_, high) = bucket_bounds(index);
+                return Some(high.max(self.min).min(self.max));
+            }
+        }
+        unreachable!()
+    }
+    pub fn p50(&self) -> Option<u64> {
+        self.value_at_quantile(0.5)
+    }
+    pub fn p90(&self) -> Option<u64> {
+        self.value_at_quantile(0.9)
+    }
+    pub fn p99(&self) -> Option<u64> {
+        self.value_at_quantile(0.99)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// Histograms for a single kind of operation.
+pub struct IopKindHistograms {
+    requested_len: LogLinearHistogram,
+    returned_len: LogLinearHistogram,
+    latency_nanos: LogLinearHistogram
+}
+impl IopKindHistograms {
+    /// Returns the histogram of requested sizes (see [`crate::IopActions::requested_len()`]).
+    pub fn requested_len(&self) -> &LogLinearHistogram {
+        &self.requested_len
+    }
+    /// Returns the histogram of returned sizes of successful operations (see [`crate::IopResults::returned_len()`]).
+    pub fn returned_len(&self) -> &LogLinearHistogram {
+        &self.returned_len
+    }
+    /// Returns the histogram of operation latencies in nanoseconds.
+    ///
+    /// This is only populated for operations performed while timing was enabled on the wrapper.
+    pub fn latency_nanos(&self) -> &LogLinearHistogram {
+        &self.latency_nanos
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// A log collector that records size and latency histograms for each kind of operation, without storing the operations themselves.
+pub struct IopHistograms {
+    histograms: BTreeMap<IopKind, IopKindHistograms>
+}
+impl IopHistograms {
+    /// Returns the histograms for the given kind of operation, or `None` if no such operation was recorded.
+    pub fn get(&self, kind: IopKind) -> Option<&IopKindHistograms> {
+        self.histograms.get(&kind)
+    }
+    /// Iterate over the histograms of every recorded kind of operation.
+    pub fn iter(&self) -> impl Iterator<Item = (IopKind, &IopKindHistograms)> {
+        self.histograms.iter().map(|(kind, hists)| (*kind, hists))
+    }
+}
+impl IopCollector for IopHistograms {
+    fn collect_iop(&mut self, iop_record: IopRecord) {
+        let action = iop_record.action();
+        let hists = self.histograms.entry(action.kind()).or_default();
+        if let Some(n) = action.requested_len() {
+            hists.requested_len.record(u64::try_from(n).unwrap());
+        }
+        if let Some(n) = iop_record.result().returned_len() {
+            hists.returned_len.record(u64::try_from(n).unwrap());
+        }
+        if let Some(timing) = iop_record.timing() {
+            hists.latency_nanos.record(
+                u64::try_from(timing.elapsed().as_nanos()).unwrap_or(u64::MAX));
+        }
+    }
+}
@@ -1,6 +1,6 @@
 #![doc(html_root_url = "https://docs.rs/io_wrapper_statistics/0.1.1")]
 
-use std::io::{Read, Write, Seek, SeekFrom};
+use std::io::{Read, BufRead, Write, Seek, SeekFrom};
 use std::io::Result as IOResult;
 use std::io::ErrorKind;
 use std::io::{IoSlice, IoSliceMut};
@@ -12,14 +12,62 @@ use std::convert::TryFrom;
 
 use std::iter::Extend;
 
+use std::time::{Duration, Instant};
+
 use num_traits::{PrimInt, Unsigned, Signed};
 
 pub use success_failure_ctr::SuccessFailureCounter;
+pub use histogram::{LogLinearHistogram, IopHistograms};
+pub use collectors::{RingLog, RecordLog, NoLog, OpSummary};
+pub use shared::SharedStats;
+pub use snapshot::IoStatsSnapshot;
+pub use fault::{FaultSchedule, FaultRule, FaultTrigger, SplitPolicy};
+use fault::Splitter;
+pub use retry::RetryStatistics;
+pub use replay::ReplayIo;
+pub use mock::MockIo;
+pub use trace::{TraceWriter, TraceFormat};
+pub use binary_trace::{BinaryTraceWriter, BinaryTraceReader, BinaryTraceHeader};
+pub use chrome_trace::ChromeTraceExporter;
+pub use registry::InstrumentReport;
+pub use report::StatsTable;
+use registry::Registration;
+#[cfg(feature = "tracing")]
+pub use tracing_log::TracingLog;
+#[cfg(feature = "metrics")]
+pub use metrics_export::PrometheusRenderer;
+
+pub mod histogram;
+pub mod collectors;
+pub mod shared;
+pub mod snapshot;
+pub mod fault;
+pub mod retry;
+pub mod replay;
+pub mod mock;
+pub mod trace;
+pub mod binary_trace;
+pub mod chrome_trace;
+pub mod registry;
+pub mod report;
+#[cfg(feature = "tracing")]
+pub mod tracing_log;
+#[cfg(feature = "metrics")]
+pub mod metrics_export;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "tokio")]
+mod tokio_impl;
+#[cfg(feature = "futures-io")]
+mod futures_impl;
 
 pub mod success_failure_ctr {
     use num_traits::{PrimInt, Unsigned};
 
+    use std::ops::{Add, Sub};
+
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     /// A struct for counting successful and failed attempts.
     pub struct SuccessFailureCounter<T: PrimInt + Unsigned> {
         success_ctr: T,
@@ -48,6 +96,25 @@ pub mod success_failure_ctr {
             self.success_ctr + self.failure_ctr
         }
     }
+    impl<T: PrimInt + Unsigned> Add for SuccessFailureCounter<T> {
+        type Output = Self;
+        fn add(self, other: Self) -> Self {
+            SuccessFailureCounter {
+                success_ctr: self.success_ctr + other.success_ctr,
+                failure_ctr: self.failure_ctr + other.failure_ctr
+            }
+        }
+    }
+    impl<T: PrimInt + Unsigned> Sub for SuccessFailureCounter<T> {
+        type Output = Self;
+        /// Subtracts each count, saturating at zero.
+        fn sub(self, other: Self) -> Self {
+            SuccessFailureCounter {
+                success_ctr: self.success_ctr.saturating_sub(other.success_ctr),
+                failure_ctr: self.failure_ctr.saturating_sub(other.failure_ctr)
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -82,105 +149,1010 @@ where
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Types of IO Operations.
 pub enum IopActions {
     /// Attempted read of the given size.
     Read(usize),
+    /// Attempted vectored read into the given number of buffers with the given total size.
+    ReadVectored(usize, usize),
     /// Attempted seek to the given position.
-    Seek(SeekFrom),
+    Seek(#[cfg_attr(feature = "serde", serde(with = "serde_impl::seek_from"))] SeekFrom),
     /// Attempted write of the given size.
     Write(usize),
+    /// Attempted vectored write from buffers of the given sizes.
+    WriteVectored(Vec<usize>),
     /// Attempted flush of a writer.
-    Flush
+    Flush,
+    /// Attempted fill of a reader's internal buffer.
+    FillBuf,
+    /// Consumption of the given number of bytes from a reader's internal buffer.
+    Consume(usize),
+    /// Attempted read up to and including the given delimiter byte.
+    ReadUntil(u8),
+    /// Attempted read of a line of text.
+    ReadLine,
+    /// Attempted shutdown of an asynchronous writer.
+    Shutdown
 }
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Results of IO Operations.
 ///
 /// We store only [`std::io::ErrorKind`] because [`std::io::Result`] is not clonable and `Arc<std::io::Error>` would be messy with lifetimes.
 pub enum IopResults {
     /// Result of a read operation.
-    Read(Result<usize, ErrorKind>),
+    Read(#[cfg_attr(feature = "serde", serde(with = "serde_impl::io_result"))] Result<usize, ErrorKind>),
+    /// Result of a vectored read operation.
+    ReadVectored(#[cfg_attr(feature = "serde", serde(with = "serde_impl::io_result"))] Result<usize, ErrorKind>),
     /// Result of a seek operation.
-    Seek(Result<u64, ErrorKind>),
+    Seek(#[cfg_attr(feature = "serde", serde(with = "serde_impl::io_result"))] Result<u64, ErrorKind>),
     /// Result of a write operation.
-    Write(Result<usize, ErrorKind>),
+    Write(#[cfg_attr(feature = "serde", serde(with = "serde_impl::io_result"))] Result<usize, ErrorKind>),
+    /// Result of a vectored write operation.
+    WriteVectored(#[cfg_attr(feature = "serde", serde(with = "serde_impl::io_result"))] Result<usize, ErrorKind>),
     /// Result of a flush operation.
-    Flush(Result<(), ErrorKind>)
+    Flush(#[cfg_attr(feature = "serde", serde(with = "serde_impl::io_result"))] Result<(), ErrorKind>),
+    /// Result of a buffer fill operation, containing the number of bytes available.
+    FillBuf(#[cfg_attr(feature = "serde", serde(with = "serde_impl::io_result"))] Result<usize, ErrorKind>),
+    /// Result of a consume operation, which cannot fail.
+    Consume,
+    /// Result of a delimited read operation.
+    ReadUntil(#[cfg_attr(feature = "serde", serde(with = "serde_impl::io_result"))] Result<usize, ErrorKind>),
+    /// Result of a line read operation.
+    ReadLine(#[cfg_attr(feature = "serde", serde(with = "serde_impl::io_result"))] Result<usize, ErrorKind>),
+    /// Result of a shutdown operation.
+    Shutdown(#[cfg_attr(feature = "serde", serde(with = "serde_impl::io_result"))] Result<(), ErrorKind>)
 }
 pub type IopInfoPair = (IopActions, IopResults);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Kinds of IO Operations, without their parameters or results.
+pub enum IopKind {
+    Read,
+    ReadVectored,
+    Seek,
+    Write,
+    WriteVectored,
+    Flush,
+    FillBuf,
+    Consume,
+    ReadUntil,
+    ReadLine,
+    Shutdown
+}
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+const IOP_KIND_COUNT: usize = IopKind::Shutdown as usize + 1;
+impl IopActions {
+    /// Returns the kind of this operation.
+    pub fn kind(&self) -> IopKind {
+        match self {
+            IopActions::Read(_) => IopKind::Read,
+            IopActions::ReadVectored(_, _) => IopKind::ReadVectored,
+            IopActions::Seek(_) => IopKind::Seek,
+            IopActions::Write(_) => IopKind::Write,
+            IopActions::WriteVectored(_) => IopKind::WriteVectored,
+            IopActions::Flush => IopKind::Flush,
+            IopActions::FillBuf => IopKind::FillBuf,
+            IopActions::Consume(_) => IopKind::Consume,
+            IopActions::ReadUntil(_) => IopKind::ReadUntil,
+            IopActions::ReadLine => IopKind::ReadLine,
+            IopActions::Shutdown => IopKind::Shutdown
+        }
+    }
+    /// Returns the number of bytes requested by this operation, if the operation has a fixed size.
+    pub fn requested_len(&self) -> Option<usize> {
+        match *self {
+            IopActions::Read(n) | IopActions::Write(n)
+                | IopActions::Consume(n) => Some(n),
+            IopActions::ReadVectored(_, n) => Some(n),
+            IopActions::WriteVectored(ref buf_lens) => Some(buf_lens.iter().sum()),
+            IopActions::Seek(_) | IopActions::Flush | IopActions::FillBuf
+                | IopActions::ReadUntil(_) | IopActions::ReadLine
+                | IopActions::Shutdown => None
+        }
+    }
+}
+impl IopResults {
+    /// Returns the kind of operation this is a result of.
+    pub fn kind(&self) -> IopKind {
+        match self {
+            IopResults::Read(_) => IopKind::Read,
+            IopResults::ReadVectored(_) => IopKind::ReadVectored,
+            IopResults::Seek(_) => IopKind::Seek,
+            IopResults::Write(_) => IopKind::Write,
+            IopResults::WriteVectored(_) => IopKind::WriteVectored,
+            IopResults::Flush(_) => IopKind::Flush,
+            IopResults::FillBuf(_) => IopKind::FillBuf,
+            IopResults::Consume => IopKind::Consume,
+            IopResults::ReadUntil(_) => IopKind::ReadUntil,
+            IopResults::ReadLine(_) => IopKind::ReadLine,
+            IopResults::Shutdown(_) => IopKind::Shutdown
+        }
+    }
+    /// Returns the number of bytes transferred (or made available, for [`IopResults::FillBuf`]) by a successful operation.
+    ///
+    /// Returns `None` for failed operations and for operations that do not transfer data.
+    /// [`IopResults::Consume`] also returns `None`, as the amount is stored in [`IopActions::Consume`].
+    pub fn returned_len(&self) -> Option<usize> {
+        match *self {
+            IopResults::Read(Ok(n)) | IopResults::ReadVectored(Ok(n))
+                | IopResults::Write(Ok(n)) | IopResults::WriteVectored(Ok(n))
+                | IopResults::FillBuf(Ok(n)) | IopResults::ReadUntil(Ok(n))
+                | IopResults::ReadLine(Ok(n)) => Some(n),
+            _ => None
+        }
+    }
+    /// Returns the error kind of a failed operation.
+    pub fn error_kind(&self) -> Option<ErrorKind> {
+        match *self {
+            IopResults::Read(Err(e)) | IopResults::ReadVectored(Err(e))
+                | IopResults::Write(Err(e)) | IopResults::WriteVectored(Err(e))
+                | IopResults::FillBuf(Err(e)) | IopResults::ReadUntil(Err(e))
+                | IopResults::ReadLine(Err(e)) => Some(e),
+            IopResults::Seek(Err(e)) => Some(e),
+            IopResults::Flush(Err(e)) | IopResults::Shutdown(Err(e)) => Some(e),
+            _ => None
+        }
+    }
+    /// Returns whether the operation succeeded.
+    pub fn is_ok(&self) -> bool {
+        self.error_kind().is_none()
+    }
+    /// Returns a failed result of the given kind of operation.
+    fn failure(kind: IopKind, error_kind: ErrorKind) -> IopResults {
+        match kind {
+            IopKind::Read => IopResults::Read(Err(error_kind)),
+            IopKind::ReadVectored => IopResults::ReadVectored(Err(error_kind)),
+            IopKind::Seek => IopResults::Seek(Err(error_kind)),
+            IopKind::Write => IopResults::Write(Err(error_kind)),
+            IopKind::WriteVectored => IopResults::WriteVectored(Err(error_kind)),
+            IopKind::Flush => IopResults::Flush(Err(error_kind)),
+            IopKind::FillBuf => IopResults::FillBuf(Err(error_kind)),
+            IopKind::Consume => unreachable!("consume cannot fail"),
+            IopKind::ReadUntil => IopResults::ReadUntil(Err(error_kind)),
+            IopKind::ReadLine => IopResults::ReadLine(Err(error_kind)),
+            IopKind::Shutdown => IopResults::Shutdown(Err(error_kind))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Timing information of an I/O operation.
+pub struct IopTiming {
+    start: Duration,
+    elapsed: Duration
+}
+impl IopTiming {
+    pub fn new(start: Duration, elapsed: Duration) -> IopTiming {
+        IopTiming { start, elapsed }
+    }
+    /// Returns the start of the operation, measured from the timing epoch of the wrapper.
+    pub fn start(&self) -> Duration {
+        self.start
+    }
+    /// Returns how long the operation took.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+    /// Returns the end of the operation, measured from the timing epoch of the wrapper.
+    pub fn end(&self) -> Duration {
+        self.start + self.elapsed
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Which bytes transferred by each operation to store in the log, set with [`IOStatWrapper::set_payload_capture()`].
+pub enum PayloadCapture {
+    /// Do not store any bytes. This is the default.
+    #[default]
+    None,
+    /// Store every transferred byte.
+    Full,
+    /// Store at most the given number of bytes from the start of each transfer.
+    Prefix(usize),
+    /// Store only a 64-bit FNV-1a hash of the transferred bytes (see [`IopRecord::payload_hash()`]), which is enough to compare payloads between runs.
+    Hashed
+}
+
+/// A function that redacts captured payloads before they are stored or hashed, set with [`IOStatWrapper::set_payload_redactor()`].
+///
+/// It is given the operation and the captured bytes, which it may overwrite in place.
+pub type PayloadRedactor = dyn Fn(&IopActions, &mut [u8]) + Send + Sync;
+
+/// Holds the payload redactor of a wrapper, which cannot derive [`Debug`] itself.
+struct RedactorBox(Box<PayloadRedactor>);
+impl std::fmt::Debug for RedactorBox {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("RedactorBox(..)")
+    }
+}
+
+/// Bytes copied from an operation for the log, and whether [`PayloadCapture::Prefix`] cut them short.
+struct CapturedPayload {
+    bytes: Vec<u8>,
+    truncated: bool
+}
+
+/// Names of the [`ErrorKind`]s that stored logs can represent exactly, in three parts: the kinds available in every supported Rust version, and the kinds stabilized in Rust 1.83, 1.85, and 1.87, which are only known when compiling with those versions.
+///
+/// The binary trace format stores error kinds as indices into the concatenation of the parts (see [`error_kind_names()`]), so new kinds must only be appended.
+const ERROR_KIND_NAMES: &[(ErrorKind, &str)] = &[
+    (ErrorKind::NotFound, "NotFound"),
+    (ErrorKind::PermissionDenied, "PermissionDenied"),
+    (ErrorKind::ConnectionRefused, "ConnectionRefused"),
+    (ErrorKind::ConnectionReset, "ConnectionReset"),
+    (ErrorKind::ConnectionAborted, "ConnectionAborted"),
+    (ErrorKind::NotConnected, "NotConnected"),
+    (ErrorKind::AddrInUse, "AddrInUse"),
+    (ErrorKind::AddrNotAvailable, "AddrNotAvailable"),
+    (ErrorKind::BrokenPipe, "BrokenPipe"),
+    (ErrorKind::AlreadyExists, "AlreadyExists"),
+    (ErrorKind::WouldBlock, "WouldBlock"),
+    (ErrorKind::InvalidInput, "InvalidInput"),
+    (ErrorKind::InvalidData, "InvalidData"),
+    (ErrorKind::TimedOut, "TimedOut"),
+    (ErrorKind::WriteZero, "WriteZero"),
+    (ErrorKind::Interrupted, "Interrupted"),
+    (ErrorKind::Unsupported, "Unsupported"),
+    (ErrorKind::UnexpectedEof, "UnexpectedEof"),
+    (ErrorKind::OutOfMemory, "OutOfMemory"),
+    (ErrorKind::Other, "Other")
+];
+#[rustversion::since(1.83)]
+const ERROR_KIND_NAMES_1_83: &[(ErrorKind, &str)] = &[
+    (ErrorKind::HostUnreachable, "HostUnreachable"),
+    (ErrorKind::NetworkUnreachable, "NetworkUnreachable"),
+    (ErrorKind::NetworkDown, "NetworkDown"),
+    (ErrorKind::NotADirectory, "NotADirectory"),
+    (ErrorKind::IsADirectory, "IsADirectory"),
+    (ErrorKind::DirectoryNotEmpty, "DirectoryNotEmpty"),
+    (ErrorKind::ReadOnlyFilesystem, "ReadOnlyFilesystem"),
+    (ErrorKind::StaleNetworkFileHandle, "StaleNetworkFileHandle"),
+    (ErrorKind::StorageFull, "StorageFull"),
+    (ErrorKind::NotSeekable, "NotSeekable"),
+    (ErrorKind::FileTooLarge, "FileTooLarge"),
+    (ErrorKind::ResourceBusy, "ResourceBusy"),
+    (ErrorKind::ExecutableFileBusy, "ExecutableFileBusy"),
+    (ErrorKind::Deadlock, "Deadlock"),
+    (ErrorKind::TooManyLinks, "TooManyLinks"),
+    (ErrorKind::ArgumentListTooLong, "ArgumentListTooLong")
+];
+#[rustversion::before(1.83)]
+const ERROR_KIND_NAMES_1_83: &[(ErrorKind, &str)] = &[];
+#[rustversion::since(1.85)]
+const ERROR_KIND_NAMES_1_85: &[(ErrorKind, &str)] = &[
+    (ErrorKind::QuotaExceeded, "QuotaExceeded"),
+    (ErrorKind::CrossesDevices, "CrossesDevices")
+];
+#[rustversion::before(1.85)]
+const ERROR_KIND_NAMES_1_85: &[(ErrorKind, &str)] = &[];
+#[rustversion::since(1.87)]
+const ERROR_KIND_NAMES_1_87: &[(ErrorKind, &str)] = &[
+    (ErrorKind::InvalidFilename, "InvalidFilename")
+];
+#[rustversion::before(1.87)]
+const ERROR_KIND_NAMES_1_87: &[(ErrorKind, &str)] = &[];
+
+/// Iterate over the [`ErrorKind`]s that stored logs can represent exactly, along with their names, in the order of their binary trace encoding.
+///
+/// This covers every stable error kind of the Rust version used to compile this crate, so only unstable kinds are stored as [`ErrorKind::Other`].
+pub(crate) fn error_kind_names() -> impl Iterator<Item = &'static (ErrorKind, &'static str)> {
+    ERROR_KIND_NAMES.iter()
+        .chain(ERROR_KIND_NAMES_1_83)
+        .chain(ERROR_KIND_NAMES_1_85)
+        .chain(ERROR_KIND_NAMES_1_87)
+}
+
+/// Compute the 64-bit FNV-1a hash of the given bytes.
+pub(crate) fn fnv1a_64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// An I/O operation together with its result and any extra information recorded by the wrapper.
+///
+/// Timing information is only present if timing was enabled on the wrapper when the operation was performed, and likewise for payloads and payload capture.
+/// Collectors implementing [`IopCollector`] directly receive these, while [`Extend<IopInfoPair>`] collectors only receive the [`IopRecord::info_pair()`].
+pub struct IopRecord {
+    action: IopActions,
+    result: IopResults,
+    timing: Option<IopTiming>,
+    payload: Option<Vec<u8>>,
+    payload_hash: Option<u64>,
+    payload_redacted: bool,
+    payload_truncated: bool,
+    position: Option<(u64, u64)>,
+    injected: bool
+}
+impl IopRecord {
+    pub fn new(action: IopActions, result: IopResults) -> IopRecord {
+        IopRecord {
+            action,
+            result,
+            timing: None,
+            payload: None,
+            payload_hash: None,
+            payload_redacted: false,
+            payload_truncated: false,
+            position: None,
+            injected: false
+        }
+    }
+    /// Attach the bytes transferred by this operation.
+    pub fn with_payload(mut self, payload: Vec<u8>) -> IopRecord {
+        self.payload = Some(payload);
+        self
+    }
+    /// Attach the seek positions before and after this operation.
+    pub fn with_position(mut self, position_before: u64, position_after: u64) -> IopRecord {
+        self.position = Some((position_before, position_after));
+        self
+    }
+    /// Attach the hash of the bytes transferred by this operation.
+    pub fn with_payload_hash(mut self, payload_hash: u64) -> IopRecord {
+        self.payload_hash = Some(payload_hash);
+        self
+    }
+    /// Flag the payload or payload hash of this operation as having been passed through a [`PayloadRedactor`].
+    pub fn mark_payload_redacted(mut self) -> IopRecord {
+        self.payload_redacted = true;
+        self
+    }
+    /// Flag the payload or payload hash of this operation as covering only the first bytes transferred, as with [`PayloadCapture::Prefix`].
+    pub fn mark_payload_truncated(mut self) -> IopRecord {
+        self.payload_truncated = true;
+        self
+    }
+    /// Attach timing information to this operation.
+    pub fn with_timing(mut self, timing: IopTiming) -> IopRecord {
+        self.timing = Some(timing);
+        self
+    }
+    /// Flag this operation as a failure injected by a [`FaultSchedule`].
+    pub fn mark_injected(mut self) -> IopRecord {
+        self.injected = true;
+        self
+    }
+    pub fn action(&self) -> &IopActions {
+        &self.action
+    }
+    pub fn result(&self) -> IopResults {
+        self.result
+    }
+    /// Returns the operation and its result, as logged by [`Extend<IopInfoPair>`] collectors.
+    pub fn info_pair(&self) -> IopInfoPair {
+        (self.action.clone(), self.result)
+    }
+    pub fn timing(&self) -> Option<IopTiming> {
+        self.timing
+    }
+    /// Returns the bytes transferred by this operation, if payload capture was enabled (see [`IOStatWrapper::set_payload_capture()`]).
+    ///
+    /// Reads and delimited reads capture the bytes stored in the caller's buffer, writes capture the bytes accepted by the inner I/O object, and buffer fills capture the available bytes.
+    /// With [`PayloadCapture::Prefix`], the payload may be shorter than [`IopResults::returned_len()`] (see [`IopRecord::is_payload_truncated()`]).
+    pub fn payload(&self) -> Option<&[u8]> {
+        self.payload.as_deref()
+    }
+    /// Returns the 64-bit FNV-1a hash of the (redacted) bytes transferred by this operation, if it was captured with [`PayloadCapture::Hashed`].
+    pub fn payload_hash(&self) -> Option<u64> {
+        self.payload_hash
+    }
+    /// Returns whether the payload or payload hash was computed from bytes changed by the redactor set with [`IOStatWrapper::set_payload_redactor()`].
+    pub fn is_payload_redacted(&self) -> bool {
+        self.payload_redacted
+    }
+    /// Returns whether the payload or payload hash covers only the first bytes transferred by this operation, because of [`PayloadCapture::Prefix`].
+    pub fn is_payload_truncated(&self) -> bool {
+        self.payload_truncated
+    }
+    /// Returns the seek position (see [`IOStatWrapper::seek_pos()`]) at the start of this operation.
+    pub fn position_before(&self) -> Option<u64> {
+        self.position.map(|(position_before, _)| position_before)
+    }
+    /// Returns the seek position (see [`IOStatWrapper::seek_pos()`]) after this operation.
+    pub fn position_after(&self) -> Option<u64> {
+        self.position.map(|(_, position_after)| position_after)
+    }
+    /// Returns whether the failure of this operation was injected by a [`FaultSchedule`] instead of coming from the inner I/O object.
+    pub fn is_injected(&self) -> bool {
+        self.injected
+    }
+}
+impl From<IopInfoPair> for IopRecord {
+    fn from((action, result): IopInfoPair) -> IopRecord {
+        IopRecord::new(action, result)
+    }
+}
+
+/// A destination for the operations logged by an [`IOStatWrapper`], used as its `C` parameter.
+///
+/// Every [`Extend<IopInfoPair>`] type (such as `Vec<IopInfoPair>`) is a collector of the operations and their results.
+/// Collectors that also need the timing, payload, or position of operations implement this trait directly, like [`RecordLog`].
+pub trait IopCollector {
+    /// Whether the collector ignores every operation, like [`NoLog`], so that the wrapper can skip building records and capturing payloads.
+    const DISCARDS_ALL: bool = false;
+    /// Add a completed operation to the collector.
+    fn collect_iop(&mut self, iop_record: IopRecord);
+}
+impl<C: Extend<IopInfoPair>> IopCollector for C {
+    #[inline]
+    fn collect_iop(&mut self, iop_record: IopRecord) {
+        self.extend([iop_record.info_pair()]);
+    }
+}
 
 #[derive(Debug)]
-/// A wrapper around an IO object that tracks operations and statistics.
-pub struct IOStatWrapper<T, C> {
-    inner_io: T,
+/// Everything in an [`IOStatWrapper`] except for the inner I/O object.
+///
+/// This is kept separate so that the statistics can be updated while a borrow of the inner I/O object is alive (e.g. in [`BufRead::fill_buf()`]).
+struct WrapperState<C> {
     iop_log: C,
     read_call_counter: SuccessFailureCounter<u64>,
     read_byte_counter: usize,
+    read_requested_byte_counter: usize,
+    read_returned_byte_counter: usize,
     seek_call_counter: SuccessFailureCounter<u64>,
     seek_pos: u64, // Meaningless unless T: Seek
+    iop_start_pos: u64, // Seek position at the start of the current operation
     write_call_counter: SuccessFailureCounter<u64>,
     write_flush_counter: SuccessFailureCounter<u64>,
-    write_byte_counter: usize
+    write_byte_counter: usize,
+    write_requested_byte_counter: usize,
+    seek_distance_counter: u64,
+    fill_buf_call_counter: SuccessFailureCounter<u64>,
+    fill_buf_hit_counter: u64,
+    fill_buf_miss_counter: u64,
+    fill_buf_remaining: usize, // Bytes seen in fill_buf but not yet consumed
+    read_until_call_counter: SuccessFailureCounter<u64>,
+    timing_epoch: Option<Instant>,
+    read_time: Duration,
+    seek_time: Duration,
+    write_time: Duration,
+    flush_time: Duration,
+    shared_stats: Option<SharedStats>,
+    fault_schedule: Option<FaultSchedule>,
+    read_splitter: Option<Splitter>,
+    write_splitter: Option<Splitter>,
+    retry_statistics: RetryStatistics,
+    payload_capture: PayloadCapture,
+    payload_redactor: Option<RedactorBox>,
+    name: Option<String>,
+    registration: Option<Registration>,
+    #[cfg(any(feature = "tokio", feature = "futures-io"))]
+    write_shutdown_counter: SuccessFailureCounter<u64>,
+    #[cfg(any(feature = "tokio", feature = "futures-io"))]
+    pending_counters: [u64; IOP_KIND_COUNT],
+    // Start of the first poll of each pending operation
+    #[cfg(any(feature = "tokio", feature = "futures-io"))]
+    pending_starts: [Option<Instant>; IOP_KIND_COUNT],
+    // Seek started with tokio's AsyncSeek::start_seek that has not completed yet
+    #[cfg(feature = "tokio")]
+    pending_seek: Option<SeekFrom>
+}
+impl<C> WrapperState<C> {
+    fn new(iop_log: C, start_seek_pos: u64) -> WrapperState<C> {
+        WrapperState {
+            iop_log,
+            read_call_counter: SuccessFailureCounter::default(),
+            read_byte_counter: 0,
+            read_requested_byte_counter: 0,
+            read_returned_byte_counter: 0,
+            seek_call_counter: SuccessFailureCounter::default(),
+            seek_pos: start_seek_pos,
+            iop_start_pos: start_seek_pos,
+            write_call_counter: SuccessFailureCounter::default(),
+            write_flush_counter: SuccessFailureCounter::default(),
+            write_byte_counter: 0,
+            write_requested_byte_counter: 0,
+            seek_distance_counter: 0,
+            fill_buf_call_counter: SuccessFailureCounter::default(),
+            fill_buf_hit_counter: 0,
+            fill_buf_miss_counter: 0,
+            fill_buf_remaining: 0,
+            read_until_call_counter: SuccessFailureCounter::default(),
+            timing_epoch: None,
+            read_time: Duration::default(),
+            seek_time: Duration::default(),
+            write_time: Duration::default(),
+            flush_time: Duration::default(),
+            shared_stats: None,
+            fault_schedule: None,
+            read_splitter: None,
+            write_splitter: None,
+            retry_statistics: RetryStatistics::default(),
+            payload_capture: PayloadCapture::default(),
+            payload_redactor: None,
+            name: None,
+            registration: None,
+            #[cfg(any(feature = "tokio", feature = "futures-io"))]
+            write_shutdown_counter: SuccessFailureCounter::default(),
+            #[cfg(any(feature = "tokio", feature = "futures-io"))]
+            pending_counters: [0; IOP_KIND_COUNT],
+            #[cfg(any(feature = "tokio", feature = "futures-io"))]
+            pending_starts: [None; IOP_KIND_COUNT],
+            #[cfg(feature = "tokio")]
+            pending_seek: None
+        }
+    }
+    /// Note the start of an operation, returning its start time if timing is enabled.
+    fn start_iop(&mut self) -> Option<Instant> {
+        self.iop_start_pos = self.seek_pos;
+        self.timing_epoch.map(|_| Instant::now())
+    }
+    /// Returns how many of the `len` requested bytes a read should pass on to the inner I/O object.
+    fn read_limit(&mut self, len: usize) -> usize {
+        self.read_splitter.as_mut().map_or(len, |splitter| splitter.limit(len))
+    }
+    /// Returns how many of the `len` given bytes a write should pass on to the inner I/O object.
+    fn write_limit(&mut self, len: usize) -> usize {
+        self.write_splitter.as_mut().map_or(len, |splitter| splitter.limit(len))
+    }
+    fn advance_read(&mut self, n: usize) {
+        self.read_byte_counter += n;
+        self.seek_pos += u64::try_from(n).unwrap();
+        self.fill_buf_remaining = self.fill_buf_remaining.saturating_sub(n);
+    }
+    fn advance_write(&mut self, n: usize) {
+        self.write_byte_counter += n;
+        self.seek_pos += u64::try_from(n).unwrap();
+    }
+    /// Reset every counter and cumulative duration, keeping the seek position.
+    fn reset_counters(&mut self) {
+        self.read_call_counter = SuccessFailureCounter::default();
+        self.read_byte_counter = 0;
+        self.read_requested_byte_counter = 0;
+        self.read_returned_byte_counter = 0;
+        self.seek_call_counter = SuccessFailureCounter::default();
+        self.write_call_counter = SuccessFailureCounter::default();
+        self.write_flush_counter = SuccessFailureCounter::default();
+        self.write_byte_counter = 0;
+        self.write_requested_byte_counter = 0;
+        self.seek_distance_counter = 0;
+        self.fill_buf_call_counter = SuccessFailureCounter::default();
+        self.fill_buf_hit_counter = 0;
+        self.fill_buf_miss_counter = 0;
+        self.read_until_call_counter = SuccessFailureCounter::default();
+        self.read_time = Duration::default();
+        self.seek_time = Duration::default();
+        self.write_time = Duration::default();
+        self.flush_time = Duration::default();
+        if let Some(shared_stats) = &self.shared_stats {
+            shared_stats.store(self);
+        }
+    }
+}
+impl<C: IopCollector> WrapperState<C> {
+    /// Copy the first `len` bytes of the given chunks (or fewer, for [`PayloadCapture::Prefix`]) for the log if payload capture is enabled and the collector keeps records.
+    fn capture_payload<'a, I>(&self, chunks: I, len: usize) -> Option<CapturedPayload>
+    where
+        I: IntoIterator<Item = &'a [u8]>
+    {
+        if C::DISCARDS_ALL {
+            return None;
+        }
+        let capture_len = match self.payload_capture {
+            PayloadCapture::None => return None,
+            PayloadCapture::Full | PayloadCapture::Hashed => len,
+            PayloadCapture::Prefix(prefix_len) => len.min(prefix_len)
+        };
+        let mut bytes = Vec::with_capacity(capture_len);
+        for chunk in chunks {
+            let take_len = (capture_len - bytes.len()).min(chunk.len());
+            bytes.extend_from_slice(&chunk[..take_len]);
+        }
+        Some(CapturedPayload { bytes, truncated: capture_len < len })
+    }
+    /// Append an operation to the log, along with timing information if `start` is given.
+    fn log_iop(&mut self, action: IopActions, result: IopResults, start: Option<Instant>) {
+        self.log_iop_entry(action, result, None, false, start);
+    }
+    /// Append an operation to the log along with its captured payload, if any, after redacting and possibly hashing it.
+    fn log_iop_payload(&mut self, action: IopActions, result: IopResults, payload: Option<CapturedPayload>, start: Option<Instant>) {
+        self.log_iop_entry(action, result, payload, false, start);
+    }
+    /// Update the counters for a completed operation, and pass its record to the collector unless the collector discards it anyway.
+    fn log_iop_entry(&mut self, action: IopActions, result: IopResults, payload: Option<CapturedPayload>, injected: bool, start: Option<Instant>) {
+        match (&action, result) {
+            (IopActions::Read(n), IopResults::Read(result))
+                | (IopActions::ReadVectored(_, n), IopResults::ReadVectored(result)) => {
+                self.read_requested_byte_counter += n;
+                self.read_returned_byte_counter += result.unwrap_or(0);
+            },
+            (IopActions::Write(_), _) | (IopActions::WriteVectored(_), _) => {
+                self.write_requested_byte_counter += action.requested_len().unwrap_or(0);
+            },
+            (IopActions::Seek(_), IopResults::Seek(Ok(_))) => {
+                self.seek_distance_counter += self.seek_pos.abs_diff(self.iop_start_pos);
+            },
+            _ => {}
+        }
+        let mut timing = None;
+        if let (Some(epoch), Some(start)) = (self.timing_epoch, start) {
+            let elapsed = start.elapsed();
+            let time_ctr = match &action {
+                IopActions::Read(_) | IopActions::ReadVectored(_, _)
+                    | IopActions::FillBuf | IopActions::Consume(_)
+                    | IopActions::ReadUntil(_) | IopActions::ReadLine
+                    => &mut self.read_time,
+                IopActions::Seek(_) => &mut self.seek_time,
+                IopActions::Write(_) | IopActions::WriteVectored(_)
+                    => &mut self.write_time,
+                IopActions::Flush | IopActions::Shutdown => &mut self.flush_time
+            };
+            *time_ctr += elapsed;
+            timing = Some(IopTiming::new(start.saturating_duration_since(epoch), elapsed));
+        }
+        self.retry_statistics.record(action.kind(), result.error_kind());
+        if !C::DISCARDS_ALL {
+            let mut iop_record = IopRecord::new(action, result)
+                .with_position(self.iop_start_pos, self.seek_pos);
+            if let Some(timing) = timing {
+                iop_record = iop_record.with_timing(timing);
+            }
+            if let Some(CapturedPayload { bytes: mut payload, truncated }) = payload {
+                if let Some(RedactorBox(redactor)) = &self.payload_redactor {
+                    let unredacted = payload.clone();
+                    redactor(iop_record.action(), &mut payload);
+                    if payload != unredacted {
+                        iop_record = iop_record.mark_payload_redacted();
+                    }
+                }
+                if truncated {
+                    iop_record = iop_record.mark_payload_truncated();
+                }
+                iop_record = match self.payload_capture {
+                    PayloadCapture::Hashed => iop_record.with_payload_hash(fnv1a_64(&payload)),
+                    _ => iop_record.with_payload(payload)
+                };
+            }
+            if injected {
+                iop_record = iop_record.mark_injected();
+            }
+            self.iop_log.collect_iop(iop_record);
+        }
+        if let Some(shared_stats) = &self.shared_stats {
+            shared_stats.store(self);
+        }
+    }
+    /// Consult the fault schedule before passing an operation through, returning the error to return instead if a failure is injected.
+    ///
+    /// Injected failures are counted and logged here, without reaching the inner I/O object.
+    fn inject_fault(&mut self, action: &IopActions, start: Option<Instant>) -> Option<std::io::Error> {
+        let kind = action.kind();
+        let error_kind = self.fault_schedule.as_mut()?
+            .check(kind, self.seek_pos, action.requested_len())?;
+        let call_counter = match kind {
+            IopKind::Read | IopKind::ReadVectored => &mut self.read_call_counter,
+            IopKind::Seek => &mut self.seek_call_counter,
+            IopKind::Write | IopKind::WriteVectored => &mut self.write_call_counter,
+            IopKind::Flush => &mut self.write_flush_counter,
+            IopKind::FillBuf => &mut self.fill_buf_call_counter,
+            IopKind::ReadUntil | IopKind::ReadLine => &mut self.read_until_call_counter,
+            IopKind::Consume | IopKind::Shutdown => unreachable!("{:?} operations are never injected", kind)
+        };
+        call_counter.increment_failure();
+        self.log_iop_entry(action.clone(), IopResults::failure(kind, error_kind), None, true, start);
+        Some(std::io::Error::new(error_kind, "injected fault"))
+    }
+    /// Count and log a completed fill_buf call, classifying it as a buffer hit or miss.
+    fn record_fill_buf(&mut self, fill_result: Result<&[u8], ErrorKind>, start: Option<Instant>) {
+        let (iop_result, payload) = match fill_result {
+            Ok(buf) => {
+                self.fill_buf_call_counter.increment_success();
+                if self.fill_buf_remaining > 0 {
+                    self.fill_buf_hit_counter += 1;
+                } else {
+                    self.fill_buf_miss_counter += 1;
+                }
+                self.fill_buf_remaining = buf.len();
+                (IopResults::FillBuf(Ok(buf.len())),
+                    self.capture_payload([buf], buf.len()))
+            },
+            Err(e) => {
+                self.fill_buf_call_counter.increment_failure();
+                (IopResults::FillBuf(Err(e)), None)
+            }
+        };
+        self.log_iop_payload(IopActions::FillBuf, iop_result, payload, start);
+    }
+    fn record_consume(&mut self, amt: usize, start: Option<Instant>) {
+        self.advance_read(amt);
+        self.log_iop(IopActions::Consume(amt), IopResults::Consume, start);
+    }
+}
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+impl<C> WrapperState<C> {
+    /// Count a `Poll::Pending` return, remembering the start of the first poll of the operation.
+    fn note_pending(&mut self, kind: IopKind, start: Option<Instant>) {
+        self.pending_counters[kind as usize] += 1;
+        let first_start = &mut self.pending_starts[kind as usize];
+        if first_start.is_none() {
+            *first_start = start;
+        }
+    }
+    /// Returns the start of the first poll of an operation that has now completed.
+    fn take_async_start(&mut self, kind: IopKind, start: Option<Instant>) -> Option<Instant> {
+        self.pending_starts[kind as usize].take().or(start)
+    }
+}
+
+#[derive(Debug)]
+/// A wrapper around an IO object that tracks operations and statistics.
+pub struct IOStatWrapper<T, C> {
+    inner_io: T,
+    state: WrapperState<C>
 }
 
 impl<T, C> IOStatWrapper<T, C>
 where
-    C: Default + Extend<IopInfoPair>
+    C: Default + IopCollector
 {
     /// Create a new IOStatWrapper with a manually given seek position.
     /// Detecting the seek position automatically is not possible without specialization.
     pub fn new(obj: T, start_seek_pos: u64) -> IOStatWrapper<T, C> {
+        IOStatWrapper::with_log(obj, start_seek_pos, C::default())
+    }
+}
+impl<T, C> IOStatWrapper<T, C>
+where
+    C: IopCollector
+{
+    /// Create a new IOStatWrapper with a manually given seek position and an existing log collector.
+    ///
+    /// This is needed for collectors without a [`Default`] value, such as [`TraceWriter`].
+    pub fn with_log(obj: T, start_seek_pos: u64, iop_log: C) -> IOStatWrapper<T, C> {
         IOStatWrapper {
             inner_io: obj,
-            iop_log: C::default(),
-            read_call_counter: SuccessFailureCounter::default(),
-            read_byte_counter: 0,
-            seek_call_counter: SuccessFailureCounter::default(),
-            seek_pos: start_seek_pos,
-            write_call_counter: SuccessFailureCounter::default(),
-            write_flush_counter: SuccessFailureCounter::default(),
-            write_byte_counter: 0
+            state: WrapperState::new(iop_log, start_seek_pos)
         }
     }
+}
+impl<T, C> IOStatWrapper<T, C> {
     /// Extract the original I/O object.
     pub fn into_inner(self) -> T {
         self.inner_io
     }
+    /// Extract the original I/O object and the I/O operation log.
+    pub fn into_parts(self) -> (T, C) {
+        (self.inner_io, self.state.iop_log)
+    }
     /// Get the I/O operation log containing operations and their results.
     pub fn iop_log(&self) -> &C {
-        &self.iop_log
+        &self.state.iop_log
+    }
+    /// Start recording timing information, measuring operation start times from the current instant.
+    ///
+    /// Timing is disabled by default because reading the clock adds overhead to every operation.
+    /// The cumulative durations are always counted, while the timing of each operation is only kept by collectors that receive [`IopRecord`]s, such as [`RecordLog`].
+    pub fn enable_timing(&mut self) {
+        self.enable_timing_with_epoch(Instant::now());
+    }
+    /// Start recording timing information, measuring operation start times from the given instant.
+    ///
+    /// Giving several wrappers the same epoch makes the start times in their logs comparable.
+    pub fn enable_timing_with_epoch(&mut self, epoch: Instant) {
+        self.state.timing_epoch = Some(epoch);
+    }
+    /// Stop recording timing information. The cumulative durations are kept.
+    pub fn disable_timing(&mut self) {
+        self.state.timing_epoch = None;
+    }
+    /// Returns the epoch that operation start times are measured from, or `None` if timing is disabled.
+    pub fn timing_epoch(&self) -> Option<Instant> {
+        self.state.timing_epoch
+    }
+    /// Capture every counter, cumulative duration, and the seek position at once.
+    pub fn stats_snapshot(&self) -> IoStatsSnapshot {
+        IoStatsSnapshot::from_state(&self.state)
+    }
+    /// Reset every counter and cumulative duration to zero, returning their values from before the reset.
+    ///
+    /// The seek position, the I/O operation log, the retry statistics, and the asynchronous-only counters are kept.
+    pub fn reset_stats(&mut self) -> IoStatsSnapshot {
+        let snapshot = self.stats_snapshot();
+        self.state.reset_counters();
+        snapshot
+    }
+    /// Inject failures into subsequent operations according to the given schedule, replacing any previous schedule.
+    pub fn set_fault_schedule(&mut self, schedule: FaultSchedule) {
+        self.state.fault_schedule = Some(schedule);
+    }
+    /// Stop injecting failures, returning the schedule that was in use.
+    pub fn clear_fault_schedule(&mut self) -> Option<FaultSchedule> {
+        self.state.fault_schedule.take()
+    }
+    /// Returns the fault schedule in use, if any.
+    pub fn fault_schedule(&self) -> Option<&FaultSchedule> {
+        self.state.fault_schedule.as_ref()
+    }
+    /// Shorten subsequent [`Read::read()`] and [`Read::read_vectored()`] calls according to the given policy.
+    ///
+    /// Shortened vectored reads are passed through as a plain read into the first nonempty buffer.
+    /// The operations are logged with the size requested by the caller, and the byte counters and seek position count the bytes actually transferred.
+    ///
+    /// # Panics
+    /// Panics if `policy` is a [`SplitPolicy::FixedChunk`] of zero bytes.
+    pub fn set_read_split_policy(&mut self, policy: SplitPolicy) {
+        self.state.read_splitter = Some(Splitter::new(policy));
+    }
+    /// Shorten subsequent [`Write::write()`] and [`Write::write_vectored()`] calls according to the given policy.
+    ///
+    /// Shortened vectored writes are passed through as a plain write from the first nonempty buffer.
+    /// The operations are logged with the size requested by the caller, and the byte counters and seek position count the bytes actually transferred.
+    ///
+    /// # Panics
+    /// Panics if `policy` is a [`SplitPolicy::FixedChunk`] of zero bytes.
+    pub fn set_write_split_policy(&mut self, policy: SplitPolicy) {
+        self.state.write_splitter = Some(Splitter::new(policy));
+    }
+    /// Stop shortening reads and writes.
+    pub fn clear_split_policies(&mut self) {
+        self.state.read_splitter = None;
+        self.state.write_splitter = None;
+    }
+    /// Returns the policy used to shorten reads, if any.
+    pub fn read_split_policy(&self) -> Option<SplitPolicy> {
+        self.state.read_splitter.as_ref().map(Splitter::policy)
+    }
+    /// Returns the policy used to shorten writes, if any.
+    pub fn write_split_policy(&self) -> Option<SplitPolicy> {
+        self.state.write_splitter.as_ref().map(Splitter::policy)
+    }
+    /// Store the bytes transferred by subsequent operations in the log according to the given policy.
+    ///
+    /// Payloads are only kept by collectors that receive [`IopRecord`]s, such as [`RecordLog`].
+    /// A log captured with [`PayloadCapture::Full`] can be replayed with [`ReplayIo`], and one captured with [`PayloadCapture::Prefix`] can be replayed as long as it only reads short enough payloads.
+    pub fn set_payload_capture(&mut self, payload_capture: PayloadCapture) {
+        self.state.payload_capture = payload_capture;
+    }
+    /// Returns which transferred bytes are stored in the log.
+    pub fn payload_capture(&self) -> PayloadCapture {
+        self.state.payload_capture
+    }
+    /// Pass captured payloads through the given function before they are stored or hashed, so that secrets do not end up in the log.
+    ///
+    /// Records whose payloads the function changed are flagged with [`IopRecord::is_payload_redacted()`].
+    /// They can still be replayed with [`ReplayIo`], which then serves the redacted bytes for reads and only checks the length of writes.
+    pub fn set_payload_redactor<F>(&mut self, redactor: F)
+    where
+        F: Fn(&IopActions, &mut [u8]) + Send + Sync + 'static
+    {
+        self.state.payload_redactor = Some(RedactorBox(Box::new(redactor)));
+    }
+    /// Stop redacting captured payloads.
+    pub fn clear_payload_redactor(&mut self) {
+        self.state.payload_redactor = None;
+    }
+    /// Returns statistics classifying failures as retryable or fatal and tracking how the caller retried them.
+    pub fn retry_statistics(&self) -> &RetryStatistics {
+        &self.state.retry_statistics
+    }
+    /// Returns a thread-safe handle to the statistics of this wrapper, which stays usable after the wrapper is moved elsewhere.
+    ///
+    /// The handle is created on the first call and is updated after every operation from then on, at the cost of a few atomic stores per operation.
+    pub fn shared_stats(&mut self) -> SharedStats {
+        let state = &mut self.state;
+        match &state.shared_stats {
+            Some(shared_stats) => shared_stats.clone(),
+            None => {
+                let shared_stats = SharedStats::new();
+                shared_stats.store(state);
+                state.shared_stats = Some(shared_stats.clone());
+                shared_stats
+            }
+        }
+    }
+    /// Name this wrapper, e.g. after the file or stream it wraps, to identify it in the [`registry`].
+    pub fn set_name<S: Into<String>>(&mut self, name: S) {
+        self.state.name = Some(name.into());
+        if let Some(registration) = &self.state.registration {
+            registration.set_name(self.state.name.clone());
+        }
+    }
+    pub fn name(&self) -> Option<&str> {
+        self.state.name.as_deref()
+    }
+    /// List this wrapper in the process-wide [`registry`] until it is dropped, returning its registry identifier.
+    ///
+    /// This enables [`IOStatWrapper::shared_stats()`]. Registering a wrapper again has no effect.
+    pub fn register(&mut self) -> u64 {
+        if let Some(registration) = &self.state.registration {
+            return registration.id();
+        }
+        let shared_stats = self.shared_stats();
+        let registration = Registration::new(self.state.name.clone(), shared_stats);
+        let id = registration.id();
+        self.state.registration = Some(registration);
+        id
+    }
+    /// Returns the registry identifier of this wrapper, or `None` if it is not registered.
+    pub fn registry_id(&self) -> Option<u64> {
+        self.state.registration.as_ref().map(Registration::id)
+    }
+}
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+impl<T, C> IOStatWrapper<T, C> {
+    /// Returns the number of times an asynchronous operation of the given kind returned `Poll::Pending`.
+    ///
+    /// Operations are only logged and counted in the other counters once they complete.
+    pub fn pending_counter(&self, kind: IopKind) -> u64 {
+        self.state.pending_counters[kind as usize]
+    }
+    /// Returns the number of times an asynchronous writer was shut down (with `poll_shutdown` in tokio, or `poll_close` in futures-io).
+    pub fn write_shutdown_counter(&self) -> &SuccessFailureCounter<u64> {
+        &self.state.write_shutdown_counter
     }
 }
 
-impl<T: Read, C: Extend<IopInfoPair>> Read for IOStatWrapper<T, C> {
-    //! We wrap most methods of [`Read`], including provided ones, and pass calls through to the inner I/O object.
-    //! The I/O operation log and statistics are only explicitly updated in the [`Read::read()`] function, as it is expected that the other methods are implemented with it.
+impl<T: Read, C: IopCollector> Read for IOStatWrapper<T, C> {
+    //! We wrap [`Read::read()`] and [`Read::read_vectored()`], passing calls through to the inner I/O object and updating the I/O operation log and statistics.
+    //! [`Read::read_to_end()`], [`Read::read_to_string()`], and [`Read::read_exact()`] are deliberately *not* passed through, even if the inner I/O object overrides them. Their default implementations are built on top of [`Read::read()`], so every byte they return is counted in [`IOStatWrapper::read_byte_counter()`] and [`IOStatWrapper::seek_pos()`] stays exact.
     //! Notably, we do not passthrough [`Read::bytes()`], [`Read::chain()`], and [`Read::take()`] as the structs they return have private implementation details that we need to see to have correct type generics. However, for this reason, we do not expect other [`Read`] implementations to have their own implementations either, so this shouldn't be an issue.
     fn read(&mut self, buf: &mut [u8]) -> IOResult<usize> {
         //! Passthrough for the `inner_io` read call that increments a call counter and appends a [`IopResults::Read`] object to the log.
-        let read_result = self.inner_io.read(buf);
-        let extend_item: [IopInfoPair; 1] = match read_result {
+        let start = self.state.start_iop();
+        if let Some(e) = self.state.inject_fault(&IopActions::Read(buf.len()), start) {
+            return Err(e);
+        }
+        let limit = self.state.read_limit(buf.len());
+        let read_result = self.inner_io.read(&mut buf[..limit]);
+        let (iop_result, payload) = match read_result {
             Ok(n) => {
-                self.read_call_counter.increment_success();
-                self.read_byte_counter += n;
-                self.seek_pos += u64::try_from(n).unwrap();
-                [(IopActions::Read(buf.len()),
-                    IopResults::Read(Ok(n)))]
+                self.state.read_call_counter.increment_success();
+                self.state.advance_read(n);
+                (IopResults::Read(Ok(n)), self.state.capture_payload([&buf[..n]], n))
             },
             Err(ref e) => {
-                self.read_call_counter.increment_failure();
-                [(IopActions::Read(buf.len()),
-                    IopResults::Read(Err(e.kind())))]
+                self.state.read_call_counter.increment_failure();
+                (IopResults::Read(Err(e.kind())), None)
             }
         };
-        self.iop_log.extend(extend_item);
+        self.state.log_iop_payload(IopActions::Read(buf.len()), iop_result, payload, start);
         read_result
     }
 
     #[rustversion::since(1.36)]
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> IOResult<usize> {
-        self.inner_io.read_vectored(bufs)
+        //! Passthrough for the `inner_io` vectored read call that increments the read call counter and appends a [`IopResults::ReadVectored`] object to the log.
+        let buf_count = bufs.len();
+        let total_len = bufs.iter().map(|buf| buf.len()).sum();
+        let start = self.state.start_iop();
+        if let Some(e) = self.state.inject_fault(
+                &IopActions::ReadVectored(buf_count, total_len), start) {
+            return Err(e);
+        }
+        let read_result = match self.state.read_splitter {
+            Some(_) => match bufs.iter_mut().find(|buf| !buf.is_empty()) {
+                Some(buf) => {
+                    let limit = self.state.read_limit(buf.len());
+                    self.inner_io.read(&mut buf[..limit])
+                },
+                None => self.inner_io.read_vectored(bufs)
+            },
+            None => self.inner_io.read_vectored(bufs)
+        };
+        let (iop_result, payload) = match read_result {
+            Ok(n) => {
+                self.state.read_call_counter.increment_success();
+                self.state.advance_read(n);
+                (IopResults::ReadVectored(Ok(n)),
+                    self.state.capture_payload(bufs.iter().map(|buf| &**buf), n))
+            },
+            Err(ref e) => {
+                self.state.read_call_counter.increment_failure();
+                (IopResults::ReadVectored(Err(e.kind())), None)
+            }
+        };
+        self.state.log_iop_payload(IopActions::ReadVectored(buf_count, total_len),
+            iop_result, payload, start);
+        read_result
     }
     #[rustversion::nightly]
     #[cfg(feature = "can_vector")]
@@ -193,16 +1165,8 @@ impl<T: Read, C: Extend<IopInfoPair>> Read for IOStatWrapper<T, C> {
     unsafe fn initializer(&self) -> Initializer {
         self.inner_io.initializer()
     }
-    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> IOResult<usize> {
-        self.inner_io.read_to_end(buf)
-    }
-    fn read_to_string(&mut self, buf: &mut String) -> IOResult<usize> {
-        self.inner_io.read_to_string(buf)
-    }
-    #[rustversion::since(1.6)]
-    fn read_exact(&mut self, buf: &mut [u8]) -> IOResult<()> {
-        self.inner_io.read_exact(buf)
-    }
+    // Missing: read_to_end, read_to_string, and read_exact, so that the
+    // default impls route every byte through the instrumented read()
     fn by_ref(&mut self) -> &mut Self
     where
         Self: Sized,
@@ -232,28 +1196,162 @@ impl<T: Read, C: Extend<IopInfoPair>> Read for IOStatWrapper<T, C> {
         Take{inner: self, limit}
     }*/
 }
-impl<T: Read, C> IOStatWrapper<T, C> {
-    /// Returns the number of times [`Read::read()`] was invoked.
+impl<T, C> IOStatWrapper<T, C> {
+    /// Returns the number of times [`Read::read()`] or [`Read::read_vectored()`] was invoked.
     pub fn read_call_counter(&self) -> &SuccessFailureCounter<u64> {
-        &self.read_call_counter
+        &self.state.read_call_counter
     }
-    /// Returns the total number of bytes read.
+    /// Returns the total number of bytes read, including bytes consumed through [`BufRead`] methods.
     pub fn read_byte_counter(&self) -> usize {
-        self.read_byte_counter
+        self.state.read_byte_counter
+    }
+    /// Returns the total number of bytes requested by [`Read::read()`] and [`Read::read_vectored()`] calls, whether or not they succeeded.
+    pub fn read_requested_byte_counter(&self) -> usize {
+        self.state.read_requested_byte_counter
+    }
+    /// Returns the total number of bytes returned by [`Read::read()`] and [`Read::read_vectored()`] calls, unlike [`IOStatWrapper::read_byte_counter()`] excluding bytes consumed through [`BufRead`] methods.
+    pub fn read_returned_byte_counter(&self) -> usize {
+        self.state.read_returned_byte_counter
+    }
+    /// Returns the total time spent in read operations, including [`BufRead`] methods, while timing was enabled.
+    pub fn read_time(&self) -> Duration {
+        self.state.read_time
+    }
+}
+
+impl<T: BufRead, C: IopCollector> BufRead for IOStatWrapper<T, C> {
+    //! We wrap [`BufRead::fill_buf()`], [`BufRead::consume()`], [`BufRead::read_until()`], and [`BufRead::read_line()`], passing calls through to the inner I/O object and updating the I/O operation log and statistics.
+    //! Bytes consumed through any of these methods are counted in [`IOStatWrapper::read_byte_counter()`] and advance [`IOStatWrapper::seek_pos()`].
+    //! [`BufRead::lines()`] and [`BufRead::split()`] are implemented with [`BufRead::read_line()`] and [`BufRead::read_until()`] respectively, so they are not passed through.
+    fn fill_buf(&mut self) -> IOResult<&[u8]> {
+        //! Passthrough for the `inner_io` fill_buf call that increments a call counter, counts a buffer hit or miss, and appends a [`IopResults::FillBuf`] object to the log.
+        let start = self.state.start_iop();
+        if let Some(e) = self.state.inject_fault(&IopActions::FillBuf, start) {
+            return Err(e);
+        }
+        match self.inner_io.fill_buf() {
+            Ok(buf) => {
+                self.state.record_fill_buf(Ok(buf), start);
+                Ok(buf)
+            },
+            Err(e) => {
+                self.state.record_fill_buf(Err(e.kind()), start);
+                Err(e)
+            }
+        }
+    }
+    fn consume(&mut self, amt: usize) {
+        //! Passthrough for the `inner_io` consume call that advances the byte counter and seek position and appends a [`IopResults::Consume`] object to the log.
+        let start = self.state.start_iop();
+        self.inner_io.consume(amt);
+        self.state.record_consume(amt, start);
+    }
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> IOResult<usize> {
+        //! Passthrough for the `inner_io` read_until call that increments a call counter and appends a [`IopResults::ReadUntil`] object to the log.
+        //!
+        //! Bytes appended to `buf` before an error are still counted, as they have been consumed from the inner I/O object.
+        let old_len = buf.len();
+        let start = self.state.start_iop();
+        if let Some(e) = self.state.inject_fault(&IopActions::ReadUntil(byte), start) {
+            return Err(e);
+        }
+        let read_result = self.inner_io.read_until(byte, buf);
+        let consumed = buf.len() - old_len;
+        self.record_read_until(consumed);
+        let payload = self.state.capture_payload([&buf[old_len..]], consumed);
+        let iop_result = match read_result {
+            Ok(n) => {
+                self.state.read_until_call_counter.increment_success();
+                IopResults::ReadUntil(Ok(n))
+            },
+            Err(ref e) => {
+                self.state.read_until_call_counter.increment_failure();
+                IopResults::ReadUntil(Err(e.kind()))
+            }
+        };
+        self.state.log_iop_payload(IopActions::ReadUntil(byte), iop_result, payload, start);
+        read_result
+    }
+    fn read_line(&mut self, buf: &mut String) -> IOResult<usize> {
+        //! Passthrough for the `inner_io` read_until call with a newline delimiter that increments a call counter and appends a [`IopResults::ReadLine`] object to the log.
+        //!
+        //! This mirrors the default [`BufRead::read_line()`]: if the line is not valid UTF-8, an [`ErrorKind::InvalidData`] error is returned and `buf` is left unchanged, but the bytes are still consumed and counted.
+        let mut line_buf = Vec::new();
+        let start = self.state.start_iop();
+        if let Some(e) = self.state.inject_fault(&IopActions::ReadLine, start) {
+            return Err(e);
+        }
+        let until_result = self.inner_io.read_until(b'\n', &mut line_buf);
+        self.record_read_until(line_buf.len());
+        let payload = self.state.capture_payload([&line_buf[..]], line_buf.len());
+        let read_result = match String::from_utf8(line_buf) {
+            Ok(line) => {
+                buf.push_str(&line);
+                until_result
+            },
+            Err(_) => until_result.and_then(|_| Err(std::io::Error::new(
+                ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8")))
+        };
+        let iop_result = match read_result {
+            Ok(n) => {
+                self.state.read_until_call_counter.increment_success();
+                IopResults::ReadLine(Ok(n))
+            },
+            Err(ref e) => {
+                self.state.read_until_call_counter.increment_failure();
+                IopResults::ReadLine(Err(e.kind()))
+            }
+        };
+        self.state.log_iop_payload(IopActions::ReadLine, iop_result, payload, start);
+        read_result
+    }
+}
+impl<T, C> IOStatWrapper<T, C> {
+    fn record_read_until(&mut self, consumed: usize) {
+        self.state.advance_read(consumed);
+        // The inner buffer state is unknown after a delimited read
+        self.state.fill_buf_remaining = 0;
+    }
+    /// Returns the number of times [`BufRead::fill_buf()`] was invoked.
+    pub fn fill_buf_call_counter(&self) -> &SuccessFailureCounter<u64> {
+        &self.state.fill_buf_call_counter
+    }
+    /// Returns the number of successful [`BufRead::fill_buf()`] calls that were served from already buffered data.
+    ///
+    /// A call counts as a hit when a previous [`BufRead::fill_buf()`] returned bytes that have not yet been consumed through this wrapper.
+    /// This is an estimate based on the calls the wrapper observes: reads that leave data in the inner buffer without going through [`BufRead::fill_buf()`] are not known to the wrapper, so the following call is counted as a miss.
+    pub fn fill_buf_hit_counter(&self) -> u64 {
+        self.state.fill_buf_hit_counter
+    }
+    /// Returns the number of successful [`BufRead::fill_buf()`] calls that presumably had to refill the inner buffer.
+    ///
+    /// See [`IOStatWrapper::fill_buf_hit_counter()`] for how hits and misses are distinguished.
+    pub fn fill_buf_miss_counter(&self) -> u64 {
+        self.state.fill_buf_miss_counter
+    }
+    /// Returns the number of times [`BufRead::read_until()`] or [`BufRead::read_line()`] was invoked.
+    pub fn read_until_call_counter(&self) -> &SuccessFailureCounter<u64> {
+        &self.state.read_until_call_counter
     }
 }
 
-impl<T: Seek, C: Extend<IopInfoPair>> Seek for IOStatWrapper<T, C> {
+impl<T: Seek, C: IopCollector> Seek for IOStatWrapper<T, C> {
     //! We wrap all methods of [`Seek`], including provided ones, and pass calls through to the inner I/O object.
     //! The I/O operation log and statistics are only explicitly updated in the [`Seek::seek()`] function, as it is expected that the other methods are implemented with it.
     fn seek(&mut self, pos: SeekFrom) -> IOResult<u64> {
         //! Passthrough for the `inner_io` seek call that increments a call counter and appends a [`IopResults::Seek`] object to the log.
-        let old_pos = self.seek_pos;
+        let old_pos = self.state.seek_pos;
+        let start = self.state.start_iop();
+        if let Some(e) = self.state.inject_fault(&IopActions::Seek(pos), start) {
+            return Err(e);
+        }
         let seek_result = self.inner_io.seek(pos);
-        let extend_item: [IopInfoPair; 1] = match seek_result {
+        let iop_result = match seek_result {
             Ok(n) => {
-                self.seek_call_counter.increment_success();
-                self.seek_pos = n;
+                self.state.seek_call_counter.increment_success();
+                self.state.seek_pos = n;
+                self.state.fill_buf_remaining = 0;
                 if let SeekFrom::Current(offset) = pos {
                     match abs_sign_tuple::<i64, u64>(offset) {
                         SignedAbsResult::Zero => {
@@ -267,16 +1365,14 @@ impl<T: Seek, C: Extend<IopInfoPair>> Seek for IOStatWrapper<T, C> {
                         }
                     }
                 };
-                [(IopActions::Seek(pos),
-                    IopResults::Seek(Ok(n)))]
+                IopResults::Seek(Ok(n))
             },
             Err(ref e) => {
-                self.seek_call_counter.increment_failure();
-                [(IopActions::Seek(pos),
-                    IopResults::Seek(Err(e.kind())))]
+                self.state.seek_call_counter.increment_failure();
+                IopResults::Seek(Err(e.kind()))
             }
         };
-        self.iop_log.extend(extend_item);
+        self.state.log_iop(IopActions::Seek(pos), iop_result, start);
         seek_result
     }
     #[rustversion::since(1.55)]
@@ -293,83 +1389,114 @@ impl<T: Seek, C: Extend<IopInfoPair>> Seek for IOStatWrapper<T, C> {
         self.inner_io.stream_position()
     }
 }
-impl<T: Seek, C> IOStatWrapper<T, C> {
+impl<T, C> IOStatWrapper<T, C> {
     /// Returns the number of times [`Seek::seek()`] was invoked.
     pub fn seek_call_counter(&self) -> &SuccessFailureCounter<u64> {
-        &self.seek_call_counter
+        &self.state.seek_call_counter
     }
     /// Get the current seek position without doing an actual seek operation.
     ///
     /// This is accomplished by storing a separate position integer.
     /// When debug assertions are on we assert after every seek operation that the cursor is where we expect it to be.
     pub fn seek_pos(&self) -> u64 {
-        self.seek_pos
+        self.state.seek_pos
+    }
+    /// Returns the total distance in bytes moved by successful seek operations, in either direction.
+    pub fn seek_distance_counter(&self) -> u64 {
+        self.state.seek_distance_counter
+    }
+    /// Returns the total time spent in seek operations while timing was enabled.
+    pub fn seek_time(&self) -> Duration {
+        self.state.seek_time
     }
 }
 
-impl<T: Write, C: Extend<IopInfoPair>> Write for IOStatWrapper<T, C> {
-    //! We wrap all methods of [`Write`], including provided ones, and pass calls through to the inner I/O object.
-    //! The I/O operation log and statistics are explicitly updated in the [`Write::write()`] and [`Write::flush()`] functions, as it is expected that the other methods are implemented with them.
+impl<T: Write, C: IopCollector> Write for IOStatWrapper<T, C> {
+    //! We wrap [`Write::write()`], [`Write::write_vectored()`], and [`Write::flush()`], passing calls through to the inner I/O object and updating the I/O operation log and statistics.
+    //! [`Write::write_all()`], [`Write::write_fmt()`], and `write_all_vectored()` are deliberately *not* passed through, even if the inner I/O object overrides them. Their default implementations are built on top of [`Write::write()`] and [`Write::write_vectored()`], so every byte they write is counted in [`IOStatWrapper::write_byte_counter()`] and [`IOStatWrapper::seek_pos()`] stays exact.
     fn write(&mut self, buf: &[u8]) -> IOResult<usize> {
         //! Passthrough for the `inner_io` write call that increments a call counter and appends a [`IopResults::Write`] object to the log.
-        let write_result = self.inner_io.write(buf);
-        let extend_item: [IopInfoPair; 1] = match write_result {
+        let start = self.state.start_iop();
+        if let Some(e) = self.state.inject_fault(&IopActions::Write(buf.len()), start) {
+            return Err(e);
+        }
+        let limit = self.state.write_limit(buf.len());
+        let write_result = self.inner_io.write(&buf[..limit]);
+        let (iop_result, payload) = match write_result {
             Ok(n) => {
-                self.write_call_counter.increment_success();
-                self.write_byte_counter += n;
-                self.seek_pos += u64::try_from(n).unwrap();
-                [(IopActions::Write(buf.len()),
-                    IopResults::Write(Ok(n)))]
+                self.state.write_call_counter.increment_success();
+                self.state.advance_write(n);
+                (IopResults::Write(Ok(n)), self.state.capture_payload([&buf[..n]], n))
             },
             Err(ref e) => {
-                self.write_call_counter.increment_failure();
-                [(IopActions::Write(buf.len()),
-                    IopResults::Write(Err(e.kind())))]
+                self.state.write_call_counter.increment_failure();
+                (IopResults::Write(Err(e.kind())), None)
             }
         };
-        self.iop_log.extend(extend_item);
+        self.state.log_iop_payload(IopActions::Write(buf.len()), iop_result, payload, start);
         write_result
     }
     fn flush(&mut self) -> IOResult<()> {
         //! Passthrough for the `inner_io` write call that increments a call counter and appends a [`IopResults::Flush`] object to the log.
+        let start = self.state.start_iop();
+        if let Some(e) = self.state.inject_fault(&IopActions::Flush, start) {
+            return Err(e);
+        }
         let flush_result = self.inner_io.flush();
-        let extend_item: [IopInfoPair; 1] = match flush_result {
+        let iop_result = match flush_result {
             Ok(()) => {
-                self.write_flush_counter.increment_success();
-                [(IopActions::Flush, IopResults::Flush(Ok(())))]
+                self.state.write_flush_counter.increment_success();
+                IopResults::Flush(Ok(()))
             },
             Err(ref e) => {
-                self.write_flush_counter.increment_failure();
-                [(IopActions::Flush,
-                    IopResults::Flush(Err(e.kind())))]
+                self.state.write_flush_counter.increment_failure();
+                IopResults::Flush(Err(e.kind()))
             }
         };
-        self.iop_log.extend(extend_item);
+        self.state.log_iop(IopActions::Flush, iop_result, start);
         flush_result
     }
 
     #[rustversion::since(1.36.0)]
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> IOResult<usize> {
-        self.inner_io.write_vectored(bufs)
+        //! Passthrough for the `inner_io` vectored write call that increments the write call counter and appends a [`IopResults::WriteVectored`] object to the log.
+        let action = IopActions::WriteVectored(bufs.iter().map(|buf| buf.len()).collect());
+        let start = self.state.start_iop();
+        if let Some(e) = self.state.inject_fault(&action, start) {
+            return Err(e);
+        }
+        let write_result = match self.state.write_splitter {
+            Some(_) => match bufs.iter().find(|buf| !buf.is_empty()) {
+                Some(buf) => {
+                    let limit = self.state.write_limit(buf.len());
+                    self.inner_io.write(&buf[..limit])
+                },
+                None => self.inner_io.write_vectored(bufs)
+            },
+            None => self.inner_io.write_vectored(bufs)
+        };
+        let (iop_result, payload) = match write_result {
+            Ok(n) => {
+                self.state.write_call_counter.increment_success();
+                self.state.advance_write(n);
+                (IopResults::WriteVectored(Ok(n)),
+                    self.state.capture_payload(bufs.iter().map(|buf| &**buf), n))
+            },
+            Err(ref e) => {
+                self.state.write_call_counter.increment_failure();
+                (IopResults::WriteVectored(Err(e.kind())), None)
+            }
+        };
+        self.state.log_iop_payload(action, iop_result, payload, start);
+        write_result
     }
     #[rustversion::nightly]
     #[cfg(feature = "can_vector")]
     fn is_write_vectored(&self) -> bool {
         self.inner_io.is_write_vectored()
     }
-    // Keep the original declaration even if mut is unneeded here
-    #[allow(unused_mut)]
-    fn write_all(&mut self, mut buf: &[u8]) -> IOResult<()> {
-        self.inner_io.write_all(buf)
-    }
-    #[rustversion::nightly]
-    #[cfg(feature = "write_all_vectored")]
-    fn write_all_vectored(&mut self, mut bufs: &mut [IoSlice<'_>]) -> IOResult<()> {
-        self.inner_io.write_all_vectored(bufs)
-    }
-    fn write_fmt(&mut self, fmt: std::fmt::Arguments<'_>) -> IOResult<()> {
-        self.inner_io.write_fmt(fmt)
-    }
+    // Missing: write_all, write_all_vectored, and write_fmt, so that the
+    // default impls route every byte through the instrumented write calls
     fn by_ref(&mut self) -> &mut Self
     where
         Self: Sized,
@@ -378,16 +1505,29 @@ impl<T: Write, C: Extend<IopInfoPair>> Write for IOStatWrapper<T, C> {
         self
     }
 }
-impl<T: Write, C> IOStatWrapper<T, C> {
-    /// Returns the number of times [`Write::write()`] was invoked.
+impl<T, C> IOStatWrapper<T, C> {
+    /// Returns the number of times [`Write::write()`] or [`Write::write_vectored()`] was invoked.
     pub fn write_call_counter(&self) -> &SuccessFailureCounter<u64> {
-        &self.write_call_counter
+        &self.state.write_call_counter
     }
     /// Returns the number of times [`Write::flush()`] was invoked.
     pub fn write_flush_counter(&self) -> &SuccessFailureCounter<u64> {
-        &self.write_flush_counter
+        &self.state.write_flush_counter
     }
+    /// Returns the total number of bytes written.
     pub fn write_byte_counter(&self) -> usize {
-        self.write_byte_counter
+        self.state.write_byte_counter
+    }
+    /// Returns the total number of bytes passed to [`Write::write()`] and [`Write::write_vectored()`] calls, whether or not they succeeded.
+    pub fn write_requested_byte_counter(&self) -> usize {
+        self.state.write_requested_byte_counter
+    }
+    /// Returns the total time spent in write operations while timing was enabled.
+    pub fn write_time(&self) -> Duration {
+        self.state.write_time
+    }
+    /// Returns the total time spent in flush (and asynchronous shutdown) operations while timing was enabled.
+    pub fn flush_time(&self) -> Duration {
+        self.state.flush_time
     }
 }
@@ -0,0 +1,150 @@
+//! Export of wrapper counters as labeled metrics, in the Prometheus text format or through the [`metrics`](https://docs.rs/metrics) facade.
+//!
+//! Every wrapper is identified by a `wrapper` label chosen when it is added, and call counters have a `result` label of `success` or `failure`:
+//!
+//! | Metric | Counter |
+//! |---|---|
+//! | `io_read_calls_total` | [`crate::IOStatWrapper::read_call_counter()`] |
+//! | `io_read_bytes_total` | [`crate::IOStatWrapper::read_byte_counter()`] |
+//! | `io_write_calls_total` | [`crate::IOStatWrapper::write_call_counter()`] |
+//! | `io_write_bytes_total` | [`crate::IOStatWrapper::write_byte_counter()`] |
+//! | `io_seek_calls_total` | [`crate::IOStatWrapper::seek_call_counter()`] |
+//! | `io_flush_calls_total` | [`crate::IOStatWrapper::write_flush_counter()`] |
+
+use crate::{IoStatsSnapshot, SharedStats, SuccessFailureCounter};
+
+use std::fmt;
+
+/// A metric exported for every wrapper.
+struct MetricDef {
+    name: &'static str,
+    help: &'static str,
+    value: MetricValue
+}
+enum MetricValue {
+    Calls(fn(&IoStatsSnapshot) -> &SuccessFailureCounter<u64>),
+    Bytes(fn(&IoStatsSnapshot) -> usize)
+}
+
+const METRICS: [MetricDef; 6] = [
+    MetricDef {
+        name: "io_read_calls_total",
+        help: "Number of read operations.",
+        value: MetricValue::Calls(IoStatsSnapshot::read_call_counter)
+    },
+    MetricDef {
+        name: "io_read_bytes_total",
+        help: "Number of bytes read.",
+        value: MetricValue::Bytes(IoStatsSnapshot::read_byte_counter)
+    },
+    MetricDef {
+        name: "io_write_calls_total",
+        help: "Number of write operations.",
+        value: MetricValue::Calls(IoStatsSnapshot::write_call_counter)
+    },
+    MetricDef {
+        name: "io_write_bytes_total",
+        help: "Number of bytes written.",
+        value: MetricValue::Bytes(IoStatsSnapshot::write_byte_counter)
+    },
+    MetricDef {
+        name: "io_seek_calls_total",
+        help: "Number of seek operations.",
+        value: MetricValue::Calls(IoStatsSnapshot::seek_call_counter)
+    },
+    MetricDef {
+        name: "io_flush_calls_total",
+        help: "Number of flush operations.",
+        value: MetricValue::Calls(IoStatsSnapshot::write_flush_counter)
+    }
+];
+
+/// Returns the labeled values of a metric, with `result` labels for call counters.
+fn metric_values(metric: &MetricDef, snapshot: &IoStatsSnapshot) -> Vec<(Option<&'static str>, u64)> {
+    match metric.value {
+        MetricValue::Calls(counter) => {
+            let counter = counter(snapshot);
+            vec![(Some("success"), counter.success_ctr()),
+                (Some("failure"), counter.failure_ctr())]
+        },
+        MetricValue::Bytes(counter) => vec![(None, counter(snapshot) as u64)]
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Renders the live counters of labeled wrappers in the Prometheus text exposition format, e.g. for a scrape endpoint.
+///
+/// The counters are read from [`SharedStats`] handles (see [`crate::IOStatWrapper::shared_stats()`]) on every call to [`PrometheusRenderer::render()`], so the renderer can be kept while the wrappers are used elsewhere.
+pub struct PrometheusRenderer {
+    wrappers: Vec<(String, SharedStats)>
+}
+impl PrometheusRenderer {
+    pub fn new() -> PrometheusRenderer {
+        PrometheusRenderer::default()
+    }
+    /// Add a wrapper, identified by the given `wrapper` label value.
+    ///
+    /// # Panics
+    /// Panics if another wrapper was already added with the same label, as their series could not be told apart.
+    pub fn with_stats<S: Into<String>>(mut self, wrapper_label: S, shared_stats: SharedStats) -> PrometheusRenderer {
+        let wrapper_label = wrapper_label.into();
+        if self.wrappers.iter().any(|(label, _)| *label == wrapper_label) {
+            panic!("PrometheusRenderer already has a wrapper labeled {:?}", wrapper_label);
+        }
+        self.wrappers.push((wrapper_label, shared_stats));
+        self
+    }
+    /// Returns the number of wrappers.
+    pub fn wrapper_count(&self) -> usize {
+        self.wrappers.len()
+    }
+    /// Render the current counters of every wrapper.
+    pub fn render(&self) -> String {
+        let snapshots: Vec<(&str, IoStatsSnapshot)> = self.wrappers.iter()
+            .map(|(label, shared_stats)| (label.as_str(), shared_stats.snapshot()))
+            .collect();
+        let mut text = String::new();
+        write_prometheus(&mut text, &snapshots).expect("writing to a String cannot fail");
+        text
+    }
+}
+
+/// Write the counters of the given labeled snapshots in the Prometheus text exposition format.
+fn write_prometheus<W: fmt::Write>(out: &mut W, snapshots: &[(&str, IoStatsSnapshot)]) -> fmt::Result {
+    for metric in METRICS.iter() {
+        writeln!(out, "# HELP {} {}", metric.name, metric.help)?;
+        writeln!(out, "# TYPE {} counter", metric.name)?;
+        for (wrapper_label, snapshot) in snapshots {
+            for (result, value) in metric_values(metric, snapshot) {
+                write!(out, "{}{{wrapper=\"{}\"", metric.name, escape_label(wrapper_label))?;
+                if let Some(result) = result {
+                    write!(out, ",result=\"{}\"", result)?;
+                }
+                writeln!(out, "}} {}", value)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Set the counters of a wrapper in the current [`metrics`](https://docs.rs/metrics) recorder to the current values of its [`SharedStats`], labeled with `wrapper_label`.
+///
+/// The wrapper does not publish its counters on its own, so this must be called again (e.g. periodically from a monitoring thread, or at the end of each phase of a program) to keep the recorder up to date.
+/// The counters are set to absolute values, so calling it repeatedly is harmless, but every wrapper needs its own label: wrappers recorded with the same label overwrite each other's counters.
+pub fn record_metrics(wrapper_label: &str, shared_stats: &SharedStats) {
+    let snapshot = shared_stats.snapshot();
+    for metric in METRICS.iter() {
+        for (result, value) in metric_values(metric, &snapshot) {
+            let wrapper_label = ("wrapper", wrapper_label.to_owned());
+            let counter = match result {
+                Some(result) => metrics::counter!(metric.name, &[wrapper_label, ("result", result.to_owned())]),
+                None => metrics::counter!(metric.name, &[wrapper_label])
+            };
+            counter.absolute(value);
+        }
+    }
+}
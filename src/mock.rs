@@ -0,0 +1,124 @@
+//! Scripted mock I/O objects that verify the operations performed on them.
+
+use crate::{IopActions, IopRecord, IopResults, ReplayIo};
+
+use std::io::{Read, BufRead, Write, Seek, SeekFrom, IoSlice, IoSliceMut};
+use std::io::Result as IOResult;
+
+#[derive(Debug, Clone)]
+/// An I/O object that expects an exact, ordered sequence of operations and returns scripted results.
+///
+/// Expectations are written with the same [`IopActions`] and [`IopResults`] vocabulary as the I/O operation log, and are served by the same engine as [`ReplayIo`].
+/// Successful reads without explicit data (see [`MockIo::expect_read()`]) fill the buffer with zeros, and writes without explicit data accept any bytes.
+///
+/// # Panics
+/// Every method panics if the code under test performs an operation other than the next expected one, reporting the index of the call, the expected and actual operations, the expected result, and the previous operation.
+/// Call [`MockIo::verify()`] at the end of a test to also check that no expected operations were left over.
+pub struct MockIo {
+    script: ReplayIo
+}
+impl MockIo {
+    pub fn new() -> MockIo {
+        MockIo { script: ReplayIo::scripted(Vec::<IopRecord>::new(), "mock", true) }
+    }
+    /// Expect the given operation next, and return the given result from it.
+    ///
+    /// # Panics
+    /// Panics if `result` is a result of a different kind of operation than `action`.
+    pub fn expect(self, action: IopActions, result: IopResults) -> MockIo {
+        self.expect_record(IopRecord::new(action, result))
+    }
+    /// Expect a read of `requested_len` bytes next, and return `data` from it.
+    ///
+    /// # Panics
+    /// Panics if `data` is longer than `requested_len`, as such a read could never return it.
+    pub fn expect_read(self, requested_len: usize, data: &[u8]) -> MockIo {
+        assert!(data.len() <= requested_len,
+            "mock expectation {} is a read of {} bytes returning {} bytes of data",
+            self.script.replayed_count() + self.script.remaining(), requested_len, data.len());
+        self.expect_record(IopRecord::new(IopActions::Read(requested_len),
+            IopResults::Read(Ok(data.len()))).with_payload(data.to_vec()))
+    }
+    /// Expect a write of exactly `data` next, and accept all of it.
+    pub fn expect_write(self, data: &[u8]) -> MockIo {
+        self.expect_record(IopRecord::new(IopActions::Write(data.len()),
+            IopResults::Write(Ok(data.len()))).with_payload(data.to_vec()))
+    }
+    /// Expect the given operation next, serving and checking its payload like [`ReplayIo`] does.
+    ///
+    /// # Panics
+    /// Panics if the result of `iop_record` is a result of a different kind of operation than its action.
+    pub fn expect_record(mut self, iop_record: IopRecord) -> MockIo {
+        assert_eq!(iop_record.action().kind(), iop_record.result().kind(),
+            "expected result {:?} does not match action {:?}",
+            iop_record.result(), iop_record.action());
+        self.script.push(iop_record);
+        self
+    }
+    /// Returns the number of expected operations that have been performed.
+    pub fn performed_count(&self) -> usize {
+        self.script.replayed_count()
+    }
+    /// Returns the number of expected operations that have not been performed yet.
+    pub fn remaining(&self) -> usize {
+        self.script.remaining()
+    }
+
+    /// Check that every expected operation has been performed.
+    ///
+    /// # Panics
+    /// Panics with the list of the operations that were not performed, if any.
+    pub fn verify(&self) {
+        if !self.script.is_finished() {
+            let missing: Vec<_> = self.script.iter_remaining()
+                .map(|iop_record| (iop_record.action(), iop_record.result()))
+                .collect();
+            panic!("mock expected {} more operations after operation {}: {:?}",
+                missing.len(), self.performed_count(), missing);
+        }
+    }
+}
+impl Default for MockIo {
+    fn default() -> MockIo {
+        MockIo::new()
+    }
+}
+
+impl Read for MockIo {
+    fn read(&mut self, buf: &mut [u8]) -> IOResult<usize> {
+        self.script.read(buf)
+    }
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> IOResult<usize> {
+        self.script.read_vectored(bufs)
+    }
+}
+impl BufRead for MockIo {
+    fn fill_buf(&mut self) -> IOResult<&[u8]> {
+        self.script.fill_buf()
+    }
+    fn consume(&mut self, amt: usize) {
+        self.script.consume(amt)
+    }
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> IOResult<usize> {
+        self.script.read_until(byte, buf)
+    }
+    fn read_line(&mut self, buf: &mut String) -> IOResult<usize> {
+        self.script.read_line(buf)
+    }
+}
+impl Seek for MockIo {
+    fn seek(&mut self, pos: SeekFrom) -> IOResult<u64> {
+        self.script.seek(pos)
+    }
+}
+impl Write for MockIo {
+    fn write(&mut self, buf: &[u8]) -> IOResult<usize> {
+        self.script.write(buf)
+    }
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> IOResult<usize> {
+        self.script.write_vectored(bufs)
+    }
+    fn flush(&mut self) -> IOResult<()> {
+        self.script.flush()
+    }
+}
@@ -0,0 +1,143 @@
+//! A process-wide registry of [`crate::IOStatWrapper`]s, for enumerating every instrumented I/O object.
+//!
+//! Wrappers are added with [`crate::IOStatWrapper::register()`] and stay listed as live until they are dropped or consumed (e.g. with [`crate::IOStatWrapper::into_inner()`]).
+//! Their final counters are then kept in a bounded list of recently dropped instruments (see [`set_dropped_capacity()`]).
+//! The registry holds a handle to the statistics of each live wrapper, like [`crate::IOStatWrapper::shared_stats()`], and releases it when the wrapper is dropped, keeping only the final counters.
+
+use crate::{IoStatsSnapshot, SharedStats};
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const DEFAULT_DROPPED_CAPACITY: usize = 16;
+
+#[derive(Debug)]
+struct LiveEntry {
+    id: u64,
+    name: Option<String>,
+    stats: SharedStats
+}
+
+#[derive(Debug)]
+struct Registry {
+    live: Vec<LiveEntry>,
+    dropped: VecDeque<InstrumentReport>,
+    dropped_capacity: usize
+}
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    live: Vec::new(),
+    dropped: VecDeque::new(),
+    dropped_capacity: DEFAULT_DROPPED_CAPACITY
+});
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+fn lock_registry() -> MutexGuard<'static, Registry> {
+    // The registry stays consistent even if a thread panicked while holding it
+    REGISTRY.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[derive(Debug, Clone)]
+/// The counters of a registered wrapper at the time it was listed or dropped.
+pub struct InstrumentReport {
+    id: u64,
+    name: Option<String>,
+    live: bool,
+    snapshot: IoStatsSnapshot
+}
+impl InstrumentReport {
+    /// Returns the identifier assigned to the wrapper when it was registered, which is unique within the process.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+    /// Returns the name of the wrapper (see [`crate::IOStatWrapper::set_name()`]).
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    /// Returns whether the wrapper was still live when this report was made.
+    pub fn is_live(&self) -> bool {
+        self.live
+    }
+    pub fn snapshot(&self) -> &IoStatsSnapshot {
+        &self.snapshot
+    }
+}
+
+/// Keeps a wrapper listed as live, and moves it to the recently dropped list when dropped.
+#[derive(Debug)]
+pub(crate) struct Registration {
+    id: u64
+}
+impl Registration {
+    pub(crate) fn new(name: Option<String>, stats: SharedStats) -> Registration {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        lock_registry().live.push(LiveEntry { id, name, stats });
+        Registration { id }
+    }
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+    pub(crate) fn set_name(&self, name: Option<String>) {
+        let mut registry = lock_registry();
+        if let Some(entry) = registry.live.iter_mut().find(|entry| entry.id == self.id) {
+            entry.name = name;
+        }
+    }
+}
+impl Drop for Registration {
+    fn drop(&mut self) {
+        let mut registry = lock_registry();
+        let index = match registry.live.iter().position(|entry| entry.id == self.id) {
+            Some(index) => index,
+            None => return
+        };
+        let entry = registry.live.remove(index);
+        if registry.dropped_capacity == 0 {
+            return;
+        }
+        if registry.dropped.len() == registry.dropped_capacity {
+            registry.dropped.pop_front();
+        }
+        registry.dropped.push_back(InstrumentReport {
+            id: entry.id,
+            name: entry.name,
+            live: false,
+            snapshot: entry.stats.snapshot()
+        });
+    }
+}
+
+/// List the counters of every registered wrapper that is still live, in order of registration.
+pub fn live_instruments() -> Vec<InstrumentReport> {
+    let registry = lock_registry();
+    registry.live.iter()
+        .map(|entry| InstrumentReport {
+            id: entry.id,
+            name: entry.name.clone(),
+            live: true,
+            snapshot: entry.stats.snapshot()
+        })
+        .collect()
+}
+/// List the final counters of the most recently dropped registered wrappers, from the least to the most recently dropped.
+pub fn dropped_instruments() -> Vec<InstrumentReport> {
+    lock_registry().dropped.iter().cloned().collect()
+}
+/// List the live wrappers followed by the recently dropped ones, as in [`live_instruments()`] and [`dropped_instruments()`].
+pub fn instruments() -> Vec<InstrumentReport> {
+    let mut instruments = live_instruments();
+    instruments.extend(dropped_instruments());
+    instruments
+}
+/// Set how many dropped wrappers to keep the final counters of, discarding the least recently dropped ones beyond it. The default is 16.
+pub fn set_dropped_capacity(dropped_capacity: usize) {
+    let mut registry = lock_registry();
+    registry.dropped_capacity = dropped_capacity;
+    while registry.dropped.len() > dropped_capacity {
+        registry.dropped.pop_front();
+    }
+}
+pub fn dropped_capacity() -> usize {
+    lock_registry().dropped_capacity
+}
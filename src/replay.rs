@@ -0,0 +1,276 @@
+//! Replay of a recorded I/O operation log.
+
+use crate::{IopActions, IopRecord, IopResults, fnv1a_64};
+
+use std::collections::VecDeque;
+use std::io::{Read, BufRead, Write, Seek, SeekFrom, IoSlice, IoSliceMut};
+use std::io::Result as IOResult;
+use std::io::{Error, ErrorKind};
+
+#[derive(Debug, Clone)]
+/// An I/O object that serves the exact sequence of operations recorded in an I/O operation log.
+///
+/// The log must have been recorded with [`crate::PayloadCapture::Full`], so that every successful read has its bytes available.
+/// Reads (including short reads), buffer fills, and delimited reads return the recorded bytes, seeks return the recorded positions, writes are acknowledged with the recorded lengths, and recorded failures are returned with their [`ErrorKind`].
+/// Written bytes are compared with the recorded payload or payload hash, only up to the payload length if it was truncated by [`crate::PayloadCapture::Prefix`], and only by length if it was redacted.
+///
+/// # Panics
+/// Every method panics if the replaying code diverges from the recording: if it performs a different operation, requests a different size or seek position, or writes different bytes than were recorded, or if the recording has run out.
+/// The panic message contains the index of the diverging operation and both the expected and actual operations.
+pub struct ReplayIo {
+    trace: VecDeque<IopRecord>,
+    replayed_count: usize,
+    last_action: Option<IopActions>,
+    // Bytes returned by the last replayed fill_buf that have not been consumed
+    fill_buf_data: Vec<u8>,
+    // Shared with MockIo, which reports mismatches under its own name and
+    // serves zeros for reads that were not given any data
+    label: &'static str,
+    zero_fill_payloads: bool
+}
+impl ReplayIo {
+    /// Create a replay of the given recorded operations, such as the log of an [`crate::IOStatWrapper`] with a [`crate::RecordLog`] collector.
+    pub fn new<I>(trace: I) -> ReplayIo
+    where
+        I: IntoIterator,
+        I::Item: Into<IopRecord>
+    {
+        ReplayIo::scripted(trace, "replay", false)
+    }
+    pub(crate) fn scripted<I>(trace: I, label: &'static str, zero_fill_payloads: bool) -> ReplayIo
+    where
+        I: IntoIterator,
+        I::Item: Into<IopRecord>
+    {
+        ReplayIo {
+            trace: trace.into_iter().map(Into::into).collect(),
+            replayed_count: 0,
+            last_action: None,
+            fill_buf_data: Vec::new(),
+            label,
+            zero_fill_payloads
+        }
+    }
+    pub(crate) fn push(&mut self, iop_record: IopRecord) {
+        self.trace.push_back(iop_record);
+    }
+    /// Iterate over the recorded operations that have not been replayed yet.
+    pub fn iter_remaining(&self) -> impl Iterator<Item = &IopRecord> {
+        self.trace.iter()
+    }
+    /// Returns the number of operations that have been replayed.
+    pub fn replayed_count(&self) -> usize {
+        self.replayed_count
+    }
+    /// Returns the number of recorded operations that have not been replayed yet.
+    pub fn remaining(&self) -> usize {
+        self.trace.len()
+    }
+    /// Returns whether every recorded operation has been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.trace.is_empty()
+    }
+
+    /// Take the next recorded operation, panicking unless its action is `action`.
+    fn expect(&mut self, action: IopActions) -> IopRecord {
+        let index = self.replayed_count;
+        let iop_record = match self.trace.pop_front() {
+            Some(iop_record) => iop_record,
+            None => panic!("{} diverged at operation {}: recording has ended, got {:?} (previous operation {:?})",
+                self.label, index, action, self.last_action)
+        };
+        if *iop_record.action() != action {
+            panic!("{} diverged at operation {}: expected {:?}, got {:?} (expected result {:?}, previous operation {:?}, {} operations remaining)",
+                self.label, index, iop_record.action(), action, iop_record.result(),
+                self.last_action, self.trace.len());
+        }
+        self.replayed_count += 1;
+        self.last_action = Some(action);
+        iop_record
+    }
+    /// Returns the recorded payload of a successful operation that transferred `len` bytes.
+    fn payload(&self, iop_record: &IopRecord, len: usize) -> Vec<u8> {
+        match iop_record.payload() {
+            Some(payload) if payload.len() == len => payload.to_vec(),
+            Some(payload) if iop_record.is_payload_truncated() => panic!("{} operation {} only has the first {} of {} bytes; record with PayloadCapture::Full",
+                self.label, self.replayed_count - 1, payload.len(), len),
+            Some(payload) => panic!("{} operation {} has a payload of {} bytes instead of {}",
+                self.label, self.replayed_count - 1, payload.len(), len),
+            None if len == 0 || self.zero_fill_payloads => vec![0; len],
+            None => panic!("{} operation {} has no payload; record with PayloadCapture::Full",
+                self.label, self.replayed_count - 1)
+        }
+    }
+    /// Panic because a recorded operation has a result of a different kind of operation.
+    fn unexpected_result(&self, iop_record: &IopRecord) -> ! {
+        panic!("{} operation {} has mismatched result {:?} for {:?}",
+            self.label, self.replayed_count - 1, iop_record.result(), iop_record.action())
+    }
+    /// Check that the bytes written by the replaying code match the recorded payload or payload hash, if there is one, as far as it was captured unredacted.
+    fn check_written<'a, I>(&self, iop_record: &IopRecord, chunks: I, len: usize)
+    where
+        I: IntoIterator<Item = &'a [u8]>
+    {
+        let written = chunks.into_iter().flatten().copied();
+        if let Some(payload) = iop_record.payload() {
+            let compare_len = if iop_record.is_payload_truncated() { payload.len() } else { len };
+            let written: Vec<u8> = written.take(compare_len).collect();
+            if iop_record.is_payload_redacted() {
+                // The redactor changed the recorded bytes, so only their count can be compared
+                if written.len() != payload.len() {
+                    panic!("{} diverged at operation {}: expected write of {} bytes, got {}",
+                        self.label, self.replayed_count - 1, payload.len(), written.len());
+                }
+            } else if written != payload {
+                panic!("{} diverged at operation {}: expected write of {:?}, got {:?}",
+                    self.label, self.replayed_count - 1, payload, written);
+            }
+        } else if let Some(payload_hash) = iop_record.payload_hash() {
+            // The length of the write was already checked with its action, which is all that can be checked of a redacted hash
+            if !iop_record.is_payload_redacted() {
+                let written: Vec<u8> = written.take(len).collect();
+                let written_hash = fnv1a_64(&written);
+                if written_hash != payload_hash {
+                    panic!("{} diverged at operation {}: expected write hashing to {:#018x}, got {:?} hashing to {:#018x}",
+                        self.label, self.replayed_count - 1, payload_hash, written, written_hash);
+                }
+            }
+        }
+    }
+}
+
+fn replayed_error(error_kind: ErrorKind) -> Error {
+    Error::new(error_kind, "replayed error")
+}
+
+impl Read for ReplayIo {
+    fn read(&mut self, buf: &mut [u8]) -> IOResult<usize> {
+        let iop_record = self.expect(IopActions::Read(buf.len()));
+        match iop_record.result() {
+            IopResults::Read(Ok(n)) => {
+                buf[..n].copy_from_slice(&self.payload(&iop_record, n));
+                Ok(n)
+            },
+            IopResults::Read(Err(e)) => Err(replayed_error(e)),
+            _ => self.unexpected_result(&iop_record)
+        }
+    }
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> IOResult<usize> {
+        let total_len = bufs.iter().map(|buf| buf.len()).sum();
+        let iop_record = self.expect(IopActions::ReadVectored(bufs.len(), total_len));
+        match iop_record.result() {
+            IopResults::ReadVectored(Ok(n)) => {
+                let payload = self.payload(&iop_record, n);
+                let mut remaining = &payload[..];
+                for buf in bufs.iter_mut() {
+                    let copy_len = buf.len().min(remaining.len());
+                    buf[..copy_len].copy_from_slice(&remaining[..copy_len]);
+                    remaining = &remaining[copy_len..];
+                }
+                Ok(n)
+            },
+            IopResults::ReadVectored(Err(e)) => Err(replayed_error(e)),
+            _ => self.unexpected_result(&iop_record)
+        }
+    }
+}
+
+impl BufRead for ReplayIo {
+    fn fill_buf(&mut self) -> IOResult<&[u8]> {
+        let iop_record = self.expect(IopActions::FillBuf);
+        match iop_record.result() {
+            IopResults::FillBuf(Ok(n)) => {
+                self.fill_buf_data = self.payload(&iop_record, n);
+                Ok(&self.fill_buf_data)
+            },
+            IopResults::FillBuf(Err(e)) => Err(replayed_error(e)),
+            _ => self.unexpected_result(&iop_record)
+        }
+    }
+    fn consume(&mut self, amt: usize) {
+        self.expect(IopActions::Consume(amt));
+        let amt = amt.min(self.fill_buf_data.len());
+        self.fill_buf_data.drain(..amt);
+    }
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> IOResult<usize> {
+        let iop_record = self.expect(IopActions::ReadUntil(byte));
+        match iop_record.result() {
+            IopResults::ReadUntil(Ok(n)) => {
+                buf.extend_from_slice(&self.payload(&iop_record, n));
+                Ok(n)
+            },
+            IopResults::ReadUntil(Err(e)) => {
+                // Bytes read before an error were recorded too, as they were consumed
+                buf.extend_from_slice(iop_record.payload().unwrap_or_default());
+                Err(replayed_error(e))
+            },
+            _ => self.unexpected_result(&iop_record)
+        }
+    }
+    fn read_line(&mut self, buf: &mut String) -> IOResult<usize> {
+        let iop_record = self.expect(IopActions::ReadLine);
+        match iop_record.result() {
+            IopResults::ReadLine(Ok(n)) => {
+                let line = String::from_utf8(self.payload(&iop_record, n))
+                    .unwrap_or_else(|_| panic!("{} line {} is not valid UTF-8",
+                        self.label, self.replayed_count - 1));
+                buf.push_str(&line);
+                Ok(n)
+            },
+            IopResults::ReadLine(Err(e)) => {
+                // Like BufRead::read_line, valid UTF-8 read before an I/O error is kept
+                if let Some(Ok(line)) = iop_record.payload().map(std::str::from_utf8) {
+                    if e != ErrorKind::InvalidData {
+                        buf.push_str(line);
+                    }
+                }
+                Err(replayed_error(e))
+            },
+            _ => self.unexpected_result(&iop_record)
+        }
+    }
+}
+
+impl Seek for ReplayIo {
+    fn seek(&mut self, pos: SeekFrom) -> IOResult<u64> {
+        let iop_record = self.expect(IopActions::Seek(pos));
+        match iop_record.result() {
+            IopResults::Seek(Ok(n)) => Ok(n),
+            IopResults::Seek(Err(e)) => Err(replayed_error(e)),
+            _ => self.unexpected_result(&iop_record)
+        }
+    }
+}
+
+impl Write for ReplayIo {
+    fn write(&mut self, buf: &[u8]) -> IOResult<usize> {
+        let iop_record = self.expect(IopActions::Write(buf.len()));
+        match iop_record.result() {
+            IopResults::Write(Ok(n)) => {
+                self.check_written(&iop_record, [buf], n);
+                Ok(n)
+            },
+            IopResults::Write(Err(e)) => Err(replayed_error(e)),
+            _ => self.unexpected_result(&iop_record)
+        }
+    }
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> IOResult<usize> {
+        let iop_record = self.expect(IopActions::WriteVectored(bufs.iter().map(|buf| buf.len()).collect()));
+        match iop_record.result() {
+            IopResults::WriteVectored(Ok(n)) => {
+                self.check_written(&iop_record, bufs.iter().map(|buf| &**buf), n);
+                Ok(n)
+            },
+            IopResults::WriteVectored(Err(e)) => Err(replayed_error(e)),
+            _ => self.unexpected_result(&iop_record)
+        }
+    }
+    fn flush(&mut self) -> IOResult<()> {
+        let iop_record = self.expect(IopActions::Flush);
+        match iop_record.result() {
+            IopResults::Flush(Ok(())) => Ok(()),
+            IopResults::Flush(Err(e)) => Err(replayed_error(e)),
+            _ => self.unexpected_result(&iop_record)
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! Human-readable reports of wrapper statistics.
+
+use crate::{IoStatsSnapshot, SuccessFailureCounter};
+
+use std::fmt;
+
+/// Returns the rows of a report on `snapshot`, as pairs of a label and a formatted value.
+fn report_rows(snapshot: &IoStatsSnapshot) -> Vec<(&'static str, String)> {
+    fn push_calls(rows: &mut Vec<(&'static str, String)>, labels: [&'static str; 3], ctr: &SuccessFailureCounter<u64>) {
+        rows.push((labels[0], ctr.attempt_ctr().to_string()));
+        rows.push((labels[1], ctr.success_ctr().to_string()));
+        rows.push((labels[2], ctr.failure_ctr().to_string()));
+    }
+    let mut rows = Vec::new();
+    let read_calls = snapshot.read_call_counter();
+    push_calls(&mut rows, ["read calls", "read successes", "read failures"], read_calls);
+    rows.push(("read bytes", snapshot.read_byte_counter().to_string()));
+    rows.push(("avg read request", average(snapshot.read_requested_byte_counter(), read_calls.attempt_ctr())));
+    rows.push(("avg read return", average(snapshot.read_returned_byte_counter(), read_calls.success_ctr())));
+    let write_calls = snapshot.write_call_counter();
+    push_calls(&mut rows, ["write calls", "write successes", "write failures"], write_calls);
+    rows.push(("write bytes", snapshot.write_byte_counter().to_string()));
+    rows.push(("avg write request", average(snapshot.write_requested_byte_counter(), write_calls.attempt_ctr())));
+    rows.push(("avg write return", average(snapshot.write_byte_counter(), write_calls.success_ctr())));
+    push_calls(&mut rows, ["seek calls", "seek successes", "seek failures"], snapshot.seek_call_counter());
+    rows.push(("seek distance", snapshot.seek_distance_counter().to_string()));
+    push_calls(&mut rows, ["flush calls", "flush successes", "flush failures"], snapshot.write_flush_counter());
+    rows
+}
+
+/// Format `total / count` with one decimal, or `-` if `count` is zero.
+fn average(total: usize, count: u64) -> String {
+    match count {
+        0 => "-".to_owned(),
+        count => format!("{:.1}", total as f64 / count as f64)
+    }
+}
+
+impl fmt::Display for IoStatsSnapshot {
+    /// Formats the read, write, seek, and flush counters one per line, with the average request and return sizes of reads and writes and the seek distance.
+    ///
+    /// Averages are over all calls for request sizes and over successful calls for return sizes, and are `-` if there were no such calls.
+    /// Both read averages only cover [`std::io::Read`] calls, leaving out bytes consumed through [`std::io::BufRead`] methods.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = report_rows(self);
+        let label_width = rows.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0);
+        for (label, value) in rows {
+            writeln!(f, "{:<label_width$}  {}", label, value, label_width = label_width)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// A table lining up the statistics of several wrappers side by side, one column per wrapper, e.g. to compare the layers of a stack of I/O objects.
+///
+/// The rows are those of the [`std::fmt::Display`] implementation of [`IoStatsSnapshot`].
+pub struct StatsTable {
+    columns: Vec<(String, IoStatsSnapshot)>
+}
+impl StatsTable {
+    pub fn new() -> StatsTable {
+        StatsTable::default()
+    }
+    /// Add a column with the given header, e.g. from [`crate::IOStatWrapper::stats_snapshot()`] or [`crate::SharedStats::snapshot()`].
+    pub fn with_column<S: Into<String>>(mut self, header: S, snapshot: IoStatsSnapshot) -> StatsTable {
+        self.columns.push((header.into(), snapshot));
+        self
+    }
+    /// Returns the number of columns.
+    pub fn column_count(&self) -> usize {
+        self.columns.len()
+    }
+    /// Render the table, with left-aligned row labels and right-aligned values.
+    pub fn render(&self) -> String {
+        self.to_string()
+    }
+}
+impl fmt::Display for StatsTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let columns: Vec<(&str, Vec<(&'static str, String)>)> = self.columns.iter()
+            .map(|(header, snapshot)| (header.as_str(), report_rows(snapshot)))
+            .collect();
+        let labels: Vec<&str> = report_rows(&IoStatsSnapshot::default()).into_iter()
+            .map(|(label, _)| label)
+            .collect();
+        let label_width = labels.iter().map(|label| label.chars().count()).max().unwrap_or(0);
+        let widths: Vec<usize> = columns.iter()
+            .map(|(header, rows)| rows.iter().map(|(_, value)| value.chars().count())
+                .chain(std::iter::once(header.chars().count()))
+                .max().unwrap_or(0))
+            .collect();
+
+        write!(f, "{:<label_width$}", "", label_width = label_width)?;
+        for ((header, _), width) in columns.iter().zip(&widths) {
+            write!(f, "  {:>width$}", header, width = *width)?;
+        }
+        writeln!(f)?;
+        for (i, label) in labels.iter().enumerate() {
+            write!(f, "{:<label_width$}", label, label_width = label_width)?;
+            for ((_, rows), width) in columns.iter().zip(&widths) {
+                write!(f, "  {:>width$}", rows[i].1, width = *width)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+//! Classification of failures as retryable or fatal, and tracking of how callers retry them.
+
+use crate::IopKind;
+
+use std::io::ErrorKind;
+
+/// Returns whether a failure with the given [`ErrorKind`] is expected to be retried by the caller.
+///
+/// [`ErrorKind::Interrupted`] must always be retried under the [`std::io`] contract, and [`ErrorKind::WouldBlock`] is retried once a nonblocking I/O object becomes ready again.
+pub fn is_retryable(error_kind: ErrorKind) -> bool {
+    matches!(error_kind, ErrorKind::Interrupted | ErrorKind::WouldBlock)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// Statistics on failures and retries of an [`crate::IOStatWrapper`], returned by [`crate::IOStatWrapper::retry_statistics()`].
+///
+/// An operation counts as a retry when it immediately follows a retryable failure (see [`is_retryable()`]) of the same kind of operation.
+/// An [`ErrorKind::Interrupted`] failure counts as given up when the next operation is of a different kind, which usually means the caller propagated the error instead of retrying as the [`std::io`] contract requires.
+/// A trailing [`ErrorKind::Interrupted`] failure that has not yet been followed by another operation is not counted as given up.
+pub struct RetryStatistics {
+    retryable_failure_counter: u64,
+    fatal_failure_counter: u64,
+    retry_counter: u64,
+    consecutive_retries: u64,
+    max_consecutive_retries: u64,
+    interrupted_give_up_counter: u64,
+    // Kind and error of the previous operation if it failed retryably
+    last_retryable: Option<(IopKind, ErrorKind)>
+}
+impl RetryStatistics {
+    /// Update the statistics with a completed operation.
+    pub(crate) fn record(&mut self, kind: IopKind, error_kind: Option<ErrorKind>) {
+        match self.last_retryable.take() {
+            Some((last_kind, _)) if last_kind == kind => {
+                self.retry_counter += 1;
+                self.consecutive_retries += 1;
+                self.max_consecutive_retries =
+                    self.max_consecutive_retries.max(self.consecutive_retries);
+            },
+            last_retryable => {
+                if let Some((_, ErrorKind::Interrupted)) = last_retryable {
+                    self.interrupted_give_up_counter += 1;
+                }
+                self.consecutive_retries = 0;
+            }
+        }
+        match error_kind {
+            Some(error_kind) if is_retryable(error_kind) => {
+                self.retryable_failure_counter += 1;
+                self.last_retryable = Some((kind, error_kind));
+            },
+            Some(_) => {
+                self.fatal_failure_counter += 1;
+            },
+            None => {}
+        }
+        // A completed or fatally failed operation ends the run of retries
+        if self.last_retryable.is_none() {
+            self.consecutive_retries = 0;
+        }
+    }
+    /// Returns the number of failures with a retryable [`ErrorKind`].
+    pub fn retryable_failure_counter(&self) -> u64 {
+        self.retryable_failure_counter
+    }
+    /// Returns the number of failures with any other [`ErrorKind`].
+    pub fn fatal_failure_counter(&self) -> u64 {
+        self.fatal_failure_counter
+    }
+    /// Returns the number of operations that retried a retryable failure.
+    pub fn retry_counter(&self) -> u64 {
+        self.retry_counter
+    }
+    /// Returns the longest run of back-to-back retries of a single operation.
+    pub fn max_consecutive_retries(&self) -> u64 {
+        self.max_consecutive_retries
+    }
+    /// Returns the number of [`ErrorKind::Interrupted`] failures that the caller did not retry.
+    pub fn interrupted_give_up_counter(&self) -> u64 {
+        self.interrupted_give_up_counter
+    }
+}
@@ -0,0 +1,75 @@
+//! Stable [`serde`] representations of the [`std::io`] types stored in the I/O operation log.
+//!
+//! [`SeekFrom`] is represented like an externally tagged enum (e.g. `{"Start": 0}`), and [`ErrorKind`] as the name of its variant (e.g. `"UnexpectedEof"`).
+//! Unstable error kinds are serialized with their [`Debug`] name and deserialized as [`ErrorKind::Other`], as are stable kinds that the Rust version used to compile this crate does not have.
+
+use crate::error_kind_names;
+
+use std::io::{ErrorKind, SeekFrom};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "SeekFrom")]
+enum SeekFromRepr {
+    Start(u64),
+    End(i64),
+    Current(i64)
+}
+
+/// Serialization of [`SeekFrom`], for use with `#[serde(with = "...")]`.
+pub(crate) mod seek_from {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(pos: &SeekFrom, serializer: S) -> Result<S::Ok, S::Error> {
+        match *pos {
+            SeekFrom::Start(n) => SeekFromRepr::Start(n),
+            SeekFrom::End(n) => SeekFromRepr::End(n),
+            SeekFrom::Current(n) => SeekFromRepr::Current(n)
+        }.serialize(serializer)
+    }
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SeekFrom, D::Error> {
+        Ok(match SeekFromRepr::deserialize(deserializer)? {
+            SeekFromRepr::Start(n) => SeekFrom::Start(n),
+            SeekFromRepr::End(n) => SeekFrom::End(n),
+            SeekFromRepr::Current(n) => SeekFrom::Current(n)
+        })
+    }
+}
+
+fn error_kind_name(error_kind: ErrorKind) -> String {
+    match error_kind_names().find(|(kind, _)| *kind == error_kind) {
+        Some((_, name)) => (*name).to_owned(),
+        None => format!("{:?}", error_kind)
+    }
+}
+fn error_kind_from_name(name: &str) -> ErrorKind {
+    match error_kind_names().find(|(_, kind_name)| *kind_name == name) {
+        Some((kind, _)) => *kind,
+        None => ErrorKind::Other
+    }
+}
+
+/// Serialization of an operation result holding an [`ErrorKind`], for use with `#[serde(with = "...")]`.
+pub(crate) mod io_result {
+    use super::*;
+
+    pub(crate) fn serialize<T, S>(result: &Result<T, ErrorKind>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer
+    {
+        match result {
+            Ok(value) => Ok::<&T, String>(value),
+            Err(error_kind) => Err(error_kind_name(*error_kind))
+        }.serialize(serializer)
+    }
+    pub(crate) fn deserialize<'de, T, D>(deserializer: D) -> Result<Result<T, ErrorKind>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>
+    {
+        Ok(Result::<T, String>::deserialize(deserializer)?
+            .map_err(|name| error_kind_from_name(&name)))
+    }
+}
@@ -0,0 +1,201 @@
+//! Statistics that can be read from other threads while I/O proceeds.
+
+use crate::{IoStatsSnapshot, SuccessFailureCounter, WrapperState};
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[derive(Debug, Default)]
+struct AtomicSuccessFailureCounter {
+    success_ctr: AtomicU64,
+    failure_ctr: AtomicU64
+}
+impl AtomicSuccessFailureCounter {
+    fn load(&self) -> SuccessFailureCounter<u64> {
+        let mut ctr = SuccessFailureCounter::default();
+        ctr.add_successes(self.success_ctr.load(Ordering::Relaxed));
+        ctr.add_failures(self.failure_ctr.load(Ordering::Relaxed));
+        ctr
+    }
+    fn store(&self, ctr: &SuccessFailureCounter<u64>) {
+        self.success_ctr.store(ctr.success_ctr(), Ordering::Relaxed);
+        self.failure_ctr.store(ctr.failure_ctr(), Ordering::Relaxed);
+    }
+}
+
+fn store_duration(atomic: &AtomicU64, duration: Duration) {
+    atomic.store(u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX),
+        Ordering::Relaxed);
+}
+fn load_duration(atomic: &AtomicU64) -> Duration {
+    Duration::from_nanos(atomic.load(Ordering::Relaxed))
+}
+
+#[derive(Debug, Default)]
+struct AtomicStats {
+    read_call_counter: AtomicSuccessFailureCounter,
+    read_byte_counter: AtomicUsize,
+    read_requested_byte_counter: AtomicUsize,
+    read_returned_byte_counter: AtomicUsize,
+    seek_call_counter: AtomicSuccessFailureCounter,
+    seek_pos: AtomicU64,
+    write_call_counter: AtomicSuccessFailureCounter,
+    write_flush_counter: AtomicSuccessFailureCounter,
+    write_byte_counter: AtomicUsize,
+    write_requested_byte_counter: AtomicUsize,
+    seek_distance_counter: AtomicU64,
+    fill_buf_call_counter: AtomicSuccessFailureCounter,
+    fill_buf_hit_counter: AtomicU64,
+    fill_buf_miss_counter: AtomicU64,
+    read_until_call_counter: AtomicSuccessFailureCounter,
+    read_time_nanos: AtomicU64,
+    seek_time_nanos: AtomicU64,
+    write_time_nanos: AtomicU64,
+    flush_time_nanos: AtomicU64
+}
+
+#[derive(Debug, Clone)]
+/// A cloneable, thread-safe handle to the statistics of an [`crate::IOStatWrapper`].
+///
+/// Obtain one with [`crate::IOStatWrapper::shared_stats()`] before moving the wrapper into a [`std::io::BufReader`], a thread, or a decoder.
+/// The wrapper updates the handle after every operation, so it can be read concurrently from a monitoring thread.
+///
+/// Each counter is individually up to date, but counters read while I/O is in progress may come from consecutive operations.
+/// The asynchronous-only counters (pending polls and shutdowns) are not shared.
+pub struct SharedStats {
+    stats: Arc<AtomicStats>
+}
+impl SharedStats {
+    pub(crate) fn new() -> SharedStats {
+        SharedStats { stats: Arc::default() }
+    }
+    /// Copy every counter from the wrapper state into the shared atomics.
+    pub(crate) fn store<C>(&self, state: &WrapperState<C>) {
+        let stats = &self.stats;
+        stats.read_call_counter.store(&state.read_call_counter);
+        stats.read_byte_counter.store(state.read_byte_counter, Ordering::Relaxed);
+        stats.read_requested_byte_counter.store(state.read_requested_byte_counter, Ordering::Relaxed);
+        stats.read_returned_byte_counter.store(state.read_returned_byte_counter, Ordering::Relaxed);
+        stats.seek_call_counter.store(&state.seek_call_counter);
+        stats.seek_pos.store(state.seek_pos, Ordering::Relaxed);
+        stats.write_call_counter.store(&state.write_call_counter);
+        stats.write_flush_counter.store(&state.write_flush_counter);
+        stats.write_byte_counter.store(state.write_byte_counter, Ordering::Relaxed);
+        stats.write_requested_byte_counter.store(state.write_requested_byte_counter, Ordering::Relaxed);
+        stats.seek_distance_counter.store(state.seek_distance_counter, Ordering::Relaxed);
+        stats.fill_buf_call_counter.store(&state.fill_buf_call_counter);
+        stats.fill_buf_hit_counter.store(state.fill_buf_hit_counter, Ordering::Relaxed);
+        stats.fill_buf_miss_counter.store(state.fill_buf_miss_counter, Ordering::Relaxed);
+        stats.read_until_call_counter.store(&state.read_until_call_counter);
+        store_duration(&stats.read_time_nanos, state.read_time);
+        store_duration(&stats.seek_time_nanos, state.seek_time);
+        store_duration(&stats.write_time_nanos, state.write_time);
+        store_duration(&stats.flush_time_nanos, state.flush_time);
+    }
+
+    /// Capture every shared counter, cumulative duration, and the seek position.
+    ///
+    /// If I/O is in progress, the values may come from consecutive operations.
+    pub fn snapshot(&self) -> IoStatsSnapshot {
+        IoStatsSnapshot {
+            read_call_counter: self.read_call_counter(),
+            read_byte_counter: self.read_byte_counter(),
+            read_requested_byte_counter: self.read_requested_byte_counter(),
+            read_returned_byte_counter: self.read_returned_byte_counter(),
+            seek_call_counter: self.seek_call_counter(),
+            seek_pos: self.seek_pos(),
+            write_call_counter: self.write_call_counter(),
+            write_flush_counter: self.write_flush_counter(),
+            write_byte_counter: self.write_byte_counter(),
+            write_requested_byte_counter: self.write_requested_byte_counter(),
+            seek_distance_counter: self.seek_distance_counter(),
+            fill_buf_call_counter: self.fill_buf_call_counter(),
+            fill_buf_hit_counter: self.fill_buf_hit_counter(),
+            fill_buf_miss_counter: self.fill_buf_miss_counter(),
+            read_until_call_counter: self.read_until_call_counter(),
+            read_time: self.read_time(),
+            seek_time: self.seek_time(),
+            write_time: self.write_time(),
+            flush_time: self.flush_time()
+        }
+    }
+
+    /// Returns the number of read operations. See [`crate::IOStatWrapper::read_call_counter()`].
+    pub fn read_call_counter(&self) -> SuccessFailureCounter<u64> {
+        self.stats.read_call_counter.load()
+    }
+    /// Returns the total number of bytes read. See [`crate::IOStatWrapper::read_byte_counter()`].
+    pub fn read_byte_counter(&self) -> usize {
+        self.stats.read_byte_counter.load(Ordering::Relaxed)
+    }
+    /// Returns the total number of bytes requested by reads. See [`crate::IOStatWrapper::read_requested_byte_counter()`].
+    pub fn read_requested_byte_counter(&self) -> usize {
+        self.stats.read_requested_byte_counter.load(Ordering::Relaxed)
+    }
+    /// Returns the total number of bytes returned by reads. See [`crate::IOStatWrapper::read_returned_byte_counter()`].
+    pub fn read_returned_byte_counter(&self) -> usize {
+        self.stats.read_returned_byte_counter.load(Ordering::Relaxed)
+    }
+    /// Returns the number of seek operations. See [`crate::IOStatWrapper::seek_call_counter()`].
+    pub fn seek_call_counter(&self) -> SuccessFailureCounter<u64> {
+        self.stats.seek_call_counter.load()
+    }
+    /// Returns the seek position after the last operation. See [`crate::IOStatWrapper::seek_pos()`].
+    pub fn seek_pos(&self) -> u64 {
+        self.stats.seek_pos.load(Ordering::Relaxed)
+    }
+    /// Returns the total distance moved by seeks. See [`crate::IOStatWrapper::seek_distance_counter()`].
+    pub fn seek_distance_counter(&self) -> u64 {
+        self.stats.seek_distance_counter.load(Ordering::Relaxed)
+    }
+    /// Returns the number of write operations. See [`crate::IOStatWrapper::write_call_counter()`].
+    pub fn write_call_counter(&self) -> SuccessFailureCounter<u64> {
+        self.stats.write_call_counter.load()
+    }
+    /// Returns the number of flush operations. See [`crate::IOStatWrapper::write_flush_counter()`].
+    pub fn write_flush_counter(&self) -> SuccessFailureCounter<u64> {
+        self.stats.write_flush_counter.load()
+    }
+    /// Returns the total number of bytes written. See [`crate::IOStatWrapper::write_byte_counter()`].
+    pub fn write_byte_counter(&self) -> usize {
+        self.stats.write_byte_counter.load(Ordering::Relaxed)
+    }
+    /// Returns the total number of bytes passed to writes. See [`crate::IOStatWrapper::write_requested_byte_counter()`].
+    pub fn write_requested_byte_counter(&self) -> usize {
+        self.stats.write_requested_byte_counter.load(Ordering::Relaxed)
+    }
+    /// Returns the number of fill_buf operations. See [`crate::IOStatWrapper::fill_buf_call_counter()`].
+    pub fn fill_buf_call_counter(&self) -> SuccessFailureCounter<u64> {
+        self.stats.fill_buf_call_counter.load()
+    }
+    /// Returns the number of buffer hits. See [`crate::IOStatWrapper::fill_buf_hit_counter()`].
+    pub fn fill_buf_hit_counter(&self) -> u64 {
+        self.stats.fill_buf_hit_counter.load(Ordering::Relaxed)
+    }
+    /// Returns the number of buffer misses. See [`crate::IOStatWrapper::fill_buf_miss_counter()`].
+    pub fn fill_buf_miss_counter(&self) -> u64 {
+        self.stats.fill_buf_miss_counter.load(Ordering::Relaxed)
+    }
+    /// Returns the number of delimited read operations. See [`crate::IOStatWrapper::read_until_call_counter()`].
+    pub fn read_until_call_counter(&self) -> SuccessFailureCounter<u64> {
+        self.stats.read_until_call_counter.load()
+    }
+    /// Returns the total time spent in read operations. See [`crate::IOStatWrapper::read_time()`].
+    pub fn read_time(&self) -> Duration {
+        load_duration(&self.stats.read_time_nanos)
+    }
+    /// Returns the total time spent in seek operations. See [`crate::IOStatWrapper::seek_time()`].
+    pub fn seek_time(&self) -> Duration {
+        load_duration(&self.stats.seek_time_nanos)
+    }
+    /// Returns the total time spent in write operations. See [`crate::IOStatWrapper::write_time()`].
+    pub fn write_time(&self) -> Duration {
+        load_duration(&self.stats.write_time_nanos)
+    }
+    /// Returns the total time spent in flush operations. See [`crate::IOStatWrapper::flush_time()`].
+    pub fn flush_time(&self) -> Duration {
+        load_duration(&self.stats.flush_time_nanos)
+    }
+}
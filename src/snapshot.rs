@@ -0,0 +1,202 @@
+//! Point-in-time copies of wrapper statistics.
+
+use crate::{SuccessFailureCounter, WrapperState};
+
+use std::iter::Sum;
+use std::ops::{Add, Sub};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A copy of every counter, cumulative duration, and the seek position of an [`crate::IOStatWrapper`].
+///
+/// Snapshots can be subtracted to measure the I/O done by one phase of a program (`after - before`), and added or summed to aggregate several wrappers.
+/// Arithmetic applies to the counters and durations; the result keeps the seek position of the left operand, as positions of different snapshots cannot be meaningfully combined.
+/// As with [`crate::SharedStats`], the asynchronous-only counters (pending polls and shutdowns) are not included.
+/// Subtraction saturates every counter and duration at zero, so subtracting a later snapshot from an earlier one (or one taken before [`crate::IOStatWrapper::reset_stats()`]) gives zeros instead of panicking.
+pub struct IoStatsSnapshot {
+    pub(crate) read_call_counter: SuccessFailureCounter<u64>,
+    pub(crate) read_byte_counter: usize,
+    pub(crate) read_requested_byte_counter: usize,
+    pub(crate) read_returned_byte_counter: usize,
+    pub(crate) seek_call_counter: SuccessFailureCounter<u64>,
+    pub(crate) seek_pos: u64,
+    pub(crate) write_call_counter: SuccessFailureCounter<u64>,
+    pub(crate) write_flush_counter: SuccessFailureCounter<u64>,
+    pub(crate) write_byte_counter: usize,
+    pub(crate) write_requested_byte_counter: usize,
+    pub(crate) seek_distance_counter: u64,
+    pub(crate) fill_buf_call_counter: SuccessFailureCounter<u64>,
+    pub(crate) fill_buf_hit_counter: u64,
+    pub(crate) fill_buf_miss_counter: u64,
+    pub(crate) read_until_call_counter: SuccessFailureCounter<u64>,
+    pub(crate) read_time: Duration,
+    pub(crate) seek_time: Duration,
+    pub(crate) write_time: Duration,
+    pub(crate) flush_time: Duration
+}
+impl IoStatsSnapshot {
+    pub(crate) fn from_state<C>(state: &WrapperState<C>) -> IoStatsSnapshot {
+        IoStatsSnapshot {
+            read_call_counter: state.read_call_counter,
+            read_byte_counter: state.read_byte_counter,
+            read_requested_byte_counter: state.read_requested_byte_counter,
+            read_returned_byte_counter: state.read_returned_byte_counter,
+            seek_call_counter: state.seek_call_counter,
+            seek_pos: state.seek_pos,
+            write_call_counter: state.write_call_counter,
+            write_flush_counter: state.write_flush_counter,
+            write_byte_counter: state.write_byte_counter,
+            write_requested_byte_counter: state.write_requested_byte_counter,
+            seek_distance_counter: state.seek_distance_counter,
+            fill_buf_call_counter: state.fill_buf_call_counter,
+            fill_buf_hit_counter: state.fill_buf_hit_counter,
+            fill_buf_miss_counter: state.fill_buf_miss_counter,
+            read_until_call_counter: state.read_until_call_counter,
+            read_time: state.read_time,
+            seek_time: state.seek_time,
+            write_time: state.write_time,
+            flush_time: state.flush_time
+        }
+    }
+
+    /// See [`crate::IOStatWrapper::read_call_counter()`].
+    pub fn read_call_counter(&self) -> &SuccessFailureCounter<u64> {
+        &self.read_call_counter
+    }
+    /// See [`crate::IOStatWrapper::read_byte_counter()`].
+    pub fn read_byte_counter(&self) -> usize {
+        self.read_byte_counter
+    }
+    /// See [`crate::IOStatWrapper::read_requested_byte_counter()`].
+    pub fn read_requested_byte_counter(&self) -> usize {
+        self.read_requested_byte_counter
+    }
+    /// See [`crate::IOStatWrapper::read_returned_byte_counter()`].
+    pub fn read_returned_byte_counter(&self) -> usize {
+        self.read_returned_byte_counter
+    }
+    /// See [`crate::IOStatWrapper::seek_call_counter()`].
+    pub fn seek_call_counter(&self) -> &SuccessFailureCounter<u64> {
+        &self.seek_call_counter
+    }
+    /// See [`crate::IOStatWrapper::seek_pos()`].
+    pub fn seek_pos(&self) -> u64 {
+        self.seek_pos
+    }
+    /// See [`crate::IOStatWrapper::seek_distance_counter()`].
+    pub fn seek_distance_counter(&self) -> u64 {
+        self.seek_distance_counter
+    }
+    /// See [`crate::IOStatWrapper::write_call_counter()`].
+    pub fn write_call_counter(&self) -> &SuccessFailureCounter<u64> {
+        &self.write_call_counter
+    }
+    /// See [`crate::IOStatWrapper::write_flush_counter()`].
+    pub fn write_flush_counter(&self) -> &SuccessFailureCounter<u64> {
+        &self.write_flush_counter
+    }
+    /// See [`crate::IOStatWrapper::write_byte_counter()`].
+    pub fn write_byte_counter(&self) -> usize {
+        self.write_byte_counter
+    }
+    /// See [`crate::IOStatWrapper::write_requested_byte_counter()`].
+    pub fn write_requested_byte_counter(&self) -> usize {
+        self.write_requested_byte_counter
+    }
+    /// See [`crate::IOStatWrapper::fill_buf_call_counter()`].
+    pub fn fill_buf_call_counter(&self) -> &SuccessFailureCounter<u64> {
+        &self.fill_buf_call_counter
+    }
+    /// See [`crate::IOStatWrapper::fill_buf_hit_counter()`].
+    pub fn fill_buf_hit_counter(&self) -> u64 {
+        self.fill_buf_hit_counter
+    }
+    /// See [`crate::IOStatWrapper::fill_buf_miss_counter()`].
+    pub fn fill_buf_miss_counter(&self) -> u64 {
+        self.fill_buf_miss_counter
+    }
+    /// See [`crate::IOStatWrapper::read_until_call_counter()`].
+    pub fn read_until_call_counter(&self) -> &SuccessFailureCounter<u64> {
+        &self.read_until_call_counter
+    }
+    /// See [`crate::IOStatWrapper::read_time()`].
+    pub fn read_time(&self) -> Duration {
+        self.read_time
+    }
+    /// See [`crate::IOStatWrapper::seek_time()`].
+    pub fn seek_time(&self) -> Duration {
+        self.seek_time
+    }
+    /// See [`crate::IOStatWrapper::write_time()`].
+    pub fn write_time(&self) -> Duration {
+        self.write_time
+    }
+    /// See [`crate::IOStatWrapper::flush_time()`].
+    pub fn flush_time(&self) -> Duration {
+        self.flush_time
+    }
+}
+
+impl Add for IoStatsSnapshot {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        IoStatsSnapshot {
+            read_call_counter: self.read_call_counter + other.read_call_counter,
+            read_byte_counter: self.read_byte_counter + other.read_byte_counter,
+            read_requested_byte_counter: self.read_requested_byte_counter + other.read_requested_byte_counter,
+            read_returned_byte_counter: self.read_returned_byte_counter + other.read_returned_byte_counter,
+            seek_call_counter: self.seek_call_counter + other.seek_call_counter,
+            seek_pos: self.seek_pos,
+            write_call_counter: self.write_call_counter + other.write_call_counter,
+            write_flush_counter: self.write_flush_counter + other.write_flush_counter,
+            write_byte_counter: self.write_byte_counter + other.write_byte_counter,
+            write_requested_byte_counter: self.write_requested_byte_counter + other.write_requested_byte_counter,
+            seek_distance_counter: self.seek_distance_counter + other.seek_distance_counter,
+            fill_buf_call_counter: self.fill_buf_call_counter + other.fill_buf_call_counter,
+            fill_buf_hit_counter: self.fill_buf_hit_counter + other.fill_buf_hit_counter,
+            fill_buf_miss_counter: self.fill_buf_miss_counter + other.fill_buf_miss_counter,
+            read_until_call_counter: self.read_until_call_counter + other.read_until_call_counter,
+            read_time: self.read_time + other.read_time,
+            seek_time: self.seek_time + other.seek_time,
+            write_time: self.write_time + other.write_time,
+            flush_time: self.flush_time + other.flush_time
+        }
+    }
+}
+impl Sub for IoStatsSnapshot {
+    type Output = Self;
+    /// Subtracts every counter and duration, saturating at zero.
+    fn sub(self, other: Self) -> Self {
+        IoStatsSnapshot {
+            read_call_counter: self.read_call_counter - other.read_call_counter,
+            read_byte_counter: self.read_byte_counter.saturating_sub(other.read_byte_counter),
+            read_requested_byte_counter: self.read_requested_byte_counter.saturating_sub(other.read_requested_byte_counter),
+            read_returned_byte_counter: self.read_returned_byte_counter.saturating_sub(other.read_returned_byte_counter),
+            seek_call_counter: self.seek_call_counter - other.seek_call_counter,
+            seek_pos: self.seek_pos,
+            write_call_counter: self.write_call_counter - other.write_call_counter,
+            write_flush_counter: self.write_flush_counter - other.write_flush_counter,
+            write_byte_counter: self.write_byte_counter.saturating_sub(other.write_byte_counter),
+            write_requested_byte_counter: self.write_requested_byte_counter.saturating_sub(other.write_requested_byte_counter),
+            seek_distance_counter: self.seek_distance_counter.saturating_sub(other.seek_distance_counter),
+            fill_buf_call_counter: self.fill_buf_call_counter - other.fill_buf_call_counter,
+            fill_buf_hit_counter: self.fill_buf_hit_counter.saturating_sub(other.fill_buf_hit_counter),
+            fill_buf_miss_counter: self.fill_buf_miss_counter.saturating_sub(other.fill_buf_miss_counter),
+            read_until_call_counter: self.read_until_call_counter - other.read_until_call_counter,
+            read_time: self.read_time.saturating_sub(other.read_time),
+            seek_time: self.seek_time.saturating_sub(other.seek_time),
+            write_time: self.write_time.saturating_sub(other.write_time),
+            flush_time: self.flush_time.saturating_sub(other.flush_time)
+        }
+    }
+}
+impl Sum for IoStatsSnapshot {
+    /// Sums the counters of every snapshot. The seek position of the result is that of the first snapshot, or zero if there are none.
+    fn sum<I: Iterator<Item = IoStatsSnapshot>>(mut iter: I) -> IoStatsSnapshot {
+        match iter.next() {
+            Some(first) => iter.fold(first, |total, snapshot| total + snapshot),
+            None => IoStatsSnapshot::default()
+        }
+    }
+}
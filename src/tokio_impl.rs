@@ -0,0 +1,212 @@
+//! Implementations of the [`tokio`] I/O traits for [`IOStatWrapper`].
+//!
+//! Operations are logged and counted once they complete, and every `Poll::Pending` return is counted in [`IOStatWrapper::pending_counter()`].
+//! When timing is enabled, the start of an operation is the start of its first poll.
+//! The inner I/O object and log collector must be [`Unpin`].
+
+use crate::{IOStatWrapper, IopActions, IopResults, IopCollector, IopKind};
+
+use std::io::{IoSlice, SeekFrom};
+use std::io::Result as IOResult;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncSeek, AsyncWrite, ReadBuf};
+
+impl<T, C> AsyncRead for IOStatWrapper<T, C>
+where
+    T: AsyncRead + Unpin,
+    C: IopCollector + Unpin
+{
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<IOResult<()>> {
+        //! Passthrough for the `inner_io` poll_read call that increments a call counter and appends a [`IopResults::Read`] object to the log once the read completes.
+        let this = self.get_mut();
+        let request_len = buf.remaining();
+        let old_filled = buf.filled().len();
+        let start = this.state.start_iop();
+        let read_poll = Pin::new(&mut this.inner_io).poll_read(cx, buf);
+        let (iop_result, payload) = match read_poll {
+            Poll::Pending => {
+                this.state.note_pending(IopKind::Read, start);
+                return Poll::Pending;
+            },
+            Poll::Ready(Ok(())) => {
+                let n = buf.filled().len() - old_filled;
+                this.state.read_call_counter.increment_success();
+                this.state.advance_read(n);
+                (IopResults::Read(Ok(n)),
+                    this.state.capture_payload([&buf.filled()[old_filled..]], n))
+            },
+            Poll::Ready(Err(ref e)) => {
+                this.state.read_call_counter.increment_failure();
+                (IopResults::Read(Err(e.kind())), None)
+            }
+        };
+        let start = this.state.take_async_start(IopKind::Read, start);
+        this.state.log_iop_payload(IopActions::Read(request_len), iop_result, payload, start);
+        read_poll
+    }
+}
+
+impl<T, C> AsyncSeek for IOStatWrapper<T, C>
+where
+    T: AsyncSeek + Unpin,
+    C: IopCollector + Unpin
+{
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> IOResult<()> {
+        //! Passthrough for the `inner_io` start_seek call. The seek is logged when it completes in [`AsyncSeek::poll_complete()`], or immediately if it fails to start.
+        let this = self.get_mut();
+        let start = this.state.start_iop();
+        let start_result = Pin::new(&mut this.inner_io).start_seek(position);
+        match start_result {
+            Ok(()) => {
+                this.state.pending_seek = Some(position);
+                this.state.pending_starts[IopKind::Seek as usize] = start;
+            },
+            Err(ref e) => {
+                this.state.seek_call_counter.increment_failure();
+                this.state.log_iop(IopActions::Seek(position),
+                    IopResults::Seek(Err(e.kind())), start);
+            }
+        }
+        start_result
+    }
+    fn poll_complete(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IOResult<u64>> {
+        //! Passthrough for the `inner_io` poll_complete call that increments a call counter and appends a [`IopResults::Seek`] object to the log once a started seek completes.
+        //!
+        //! Calls without a preceding [`AsyncSeek::start_seek()`] only query the position and are not logged.
+        let this = self.get_mut();
+        let start = this.state.start_iop();
+        let seek_poll = Pin::new(&mut this.inner_io).poll_complete(cx);
+        let position = match this.state.pending_seek {
+            Some(position) => position,
+            None => return seek_poll
+        };
+        let iop_result = match seek_poll {
+            Poll::Pending => {
+                this.state.note_pending(IopKind::Seek, start);
+                return Poll::Pending;
+            },
+            Poll::Ready(Ok(n)) => {
+                this.state.seek_call_counter.increment_success();
+                this.state.seek_pos = n;
+                this.state.fill_buf_remaining = 0;
+                IopResults::Seek(Ok(n))
+            },
+            Poll::Ready(Err(ref e)) => {
+                this.state.seek_call_counter.increment_failure();
+                IopResults::Seek(Err(e.kind()))
+            }
+        };
+        this.state.pending_seek = None;
+        let start = this.state.take_async_start(IopKind::Seek, start);
+        this.state.log_iop(IopActions::Seek(position), iop_result, start);
+        seek_poll
+    }
+}
+
+impl<T, C> AsyncWrite for IOStatWrapper<T, C>
+where
+    T: AsyncWrite + Unpin,
+    C: IopCollector + Unpin
+{
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<IOResult<usize>> {
+        //! Passthrough for the `inner_io` poll_write call that increments a call counter and appends a [`IopResults::Write`] object to the log once the write completes.
+        let this = self.get_mut();
+        let start = this.state.start_iop();
+        let write_poll = Pin::new(&mut this.inner_io).poll_write(cx, buf);
+        let (iop_result, payload) = match write_poll {
+            Poll::Pending => {
+                this.state.note_pending(IopKind::Write, start);
+                return Poll::Pending;
+            },
+            Poll::Ready(Ok(n)) => {
+                this.state.write_call_counter.increment_success();
+                this.state.advance_write(n);
+                (IopResults::Write(Ok(n)), this.state.capture_payload([&buf[..n]], n))
+            },
+            Poll::Ready(Err(ref e)) => {
+                this.state.write_call_counter.increment_failure();
+                (IopResults::Write(Err(e.kind())), None)
+            }
+        };
+        let start = this.state.take_async_start(IopKind::Write, start);
+        this.state.log_iop_payload(IopActions::Write(buf.len()), iop_result, payload, start);
+        write_poll
+    }
+    fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &[IoSlice<'_>]) -> Poll<IOResult<usize>> {
+        //! Passthrough for the `inner_io` poll_write_vectored call that increments the write call counter and appends a [`IopResults::WriteVectored`] object to the log once the write completes.
+        let this = self.get_mut();
+        let start = this.state.start_iop();
+        let write_poll = Pin::new(&mut this.inner_io).poll_write_vectored(cx, bufs);
+        let (iop_result, payload) = match write_poll {
+            Poll::Pending => {
+                this.state.note_pending(IopKind::WriteVectored, start);
+                return Poll::Pending;
+            },
+            Poll::Ready(Ok(n)) => {
+                this.state.write_call_counter.increment_success();
+                this.state.advance_write(n);
+                (IopResults::WriteVectored(Ok(n)),
+                    this.state.capture_payload(bufs.iter().map(|buf| &**buf), n))
+            },
+            Poll::Ready(Err(ref e)) => {
+                this.state.write_call_counter.increment_failure();
+                (IopResults::WriteVectored(Err(e.kind())), None)
+            }
+        };
+        let start = this.state.take_async_start(IopKind::WriteVectored, start);
+        let buf_lens = bufs.iter().map(|buf| buf.len()).collect();
+        this.state.log_iop_payload(IopActions::WriteVectored(buf_lens), iop_result, payload, start);
+        write_poll
+    }
+    fn is_write_vectored(&self) -> bool {
+        self.inner_io.is_write_vectored()
+    }
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IOResult<()>> {
+        //! Passthrough for the `inner_io` poll_flush call that increments a call counter and appends a [`IopResults::Flush`] object to the log once the flush completes.
+        let this = self.get_mut();
+        let start = this.state.start_iop();
+        let flush_poll = Pin::new(&mut this.inner_io).poll_flush(cx);
+        let iop_result = match flush_poll {
+            Poll::Pending => {
+                this.state.note_pending(IopKind::Flush, start);
+                return Poll::Pending;
+            },
+            Poll::Ready(Ok(())) => {
+                this.state.write_flush_counter.increment_success();
+                IopResults::Flush(Ok(()))
+            },
+            Poll::Ready(Err(ref e)) => {
+                this.state.write_flush_counter.increment_failure();
+                IopResults::Flush(Err(e.kind()))
+            }
+        };
+        let start = this.state.take_async_start(IopKind::Flush, start);
+        this.state.log_iop(IopActions::Flush, iop_result, start);
+        flush_poll
+    }
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IOResult<()>> {
+        //! Passthrough for the `inner_io` poll_shutdown call that increments a call counter and appends a [`IopResults::Shutdown`] object to the log once the shutdown completes.
+        let this = self.get_mut();
+        let start = this.state.start_iop();
+        let shutdown_poll = Pin::new(&mut this.inner_io).poll_shutdown(cx);
+        let iop_result = match shutdown_poll {
+            Poll::Pending => {
+                this.state.note_pending(IopKind::Shutdown, start);
+                return Poll::Pending;
+            },
+            Poll::Ready(Ok(())) => {
+                this.state.write_shutdown_counter.increment_success();
+                IopResults::Shutdown(Ok(()))
+            },
+            Poll::Ready(Err(ref e)) => {
+                this.state.write_shutdown_counter.increment_failure();
+                IopResults::Shutdown(Err(e.kind()))
+            }
+        };
+        let start = this.state.take_async_start(IopKind::Shutdown, start);
+        this.state.log_iop(IopActions::Shutdown, iop_result, start);
+        shutdown_poll
+    }
+}
@@ -0,0 +1,141 @@
+//! Log collectors that stream each operation to a writer as it happens.
+
+use crate::{IopCollector, IopRecord};
+
+use std::convert::TryFrom;
+use std::fmt::Display;
+use std::io::{Error, Write};
+use std::io::Result as IOResult;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Text formats supported by [`TraceWriter`].
+///
+/// Both formats have the same fields: `seq`, `op`, `requested`, `returned`, `result`, `error_kind`, `pos_before`, `pos_after`, `start_ns`, and `elapsed_ns`.
+/// Fields that do not apply to an operation (e.g. `returned` of a failed operation, or timing of an untimed one) are `null` in JSON Lines and empty in CSV.
+pub enum TraceFormat {
+    /// One JSON object per line.
+    JsonLines,
+    /// Comma-separated values with a header line.
+    Csv
+}
+
+const CSV_HEADER: &str = "seq,op,requested,returned,result,error_kind,pos_before,pos_after,start_ns,elapsed_ns";
+
+#[derive(Debug)]
+/// A log collector that writes every operation to a [`Write`] sink as soon as it is logged, so that the trace survives a crash.
+///
+/// As [`IopCollector::collect_iop()`] cannot return errors, the first write error is stored (see [`TraceWriter::error()`]) and later operations are discarded.
+/// Use [`crate::IOStatWrapper::with_log()`] to create a wrapper with a trace writer, and [`crate::IOStatWrapper::into_parts()`] to get it back.
+pub struct TraceWriter<W: Write> {
+    sink: W,
+    format: TraceFormat,
+    flush_interval: u64,
+    seq: u64,
+    error: Option<Error>
+}
+impl<W: Write> TraceWriter<W> {
+    /// Create a trace writer that flushes the sink after every operation.
+    ///
+    /// The sink should usually be buffered (e.g. with [`std::io::BufWriter`]), as each line takes a dozen or so small formatted writes.
+    pub fn new(sink: W, format: TraceFormat) -> TraceWriter<W> {
+        TraceWriter {
+            sink,
+            format,
+            flush_interval: 1,
+            seq: 0,
+            error: None
+        }
+    }
+    /// Flush the sink after every `flush_interval` operations instead of after every operation, or never if `flush_interval` is 0.
+    pub fn with_flush_interval(mut self, flush_interval: u64) -> TraceWriter<W> {
+        self.flush_interval = flush_interval;
+        self
+    }
+    pub fn format(&self) -> TraceFormat {
+        self.format
+    }
+    /// Returns the number of operations written so far, which is also the sequence number of the next operation.
+    pub fn written_count(&self) -> u64 {
+        self.seq
+    }
+    /// Returns the first error that occurred while writing the trace, if any.
+    pub fn error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+    /// Flush the sink and return it, or return the first error that occurred while writing the trace.
+    pub fn finish(mut self) -> IOResult<W> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        self.sink.flush()?;
+        Ok(self.sink)
+    }
+
+    fn write_iop(&mut self, iop_record: &IopRecord) -> IOResult<()> {
+        if self.seq == 0 && self.format == TraceFormat::Csv {
+            writeln!(self.sink, "{}", CSV_HEADER)?;
+        }
+        let result = iop_record.result();
+        let timing = iop_record.timing();
+        let fields: [(&str, Option<String>); 10] = [
+            ("seq", Some(self.seq.to_string())),
+            ("op", Some(format!("{:?}", iop_record.action().kind()))),
+            ("requested", display(iop_record.action().requested_len())),
+            ("returned", display(result.returned_len())),
+            ("result", Some(if result.is_ok() { "ok" } else { "err" }.to_owned())),
+            ("error_kind", result.error_kind().map(|e| format!("{:?}", e))),
+            ("pos_before", display(iop_record.position_before())),
+            ("pos_after", display(iop_record.position_after())),
+            ("start_ns", display(timing.map(|timing| nanos(timing.start().as_nanos())))),
+            ("elapsed_ns", display(timing.map(|timing| nanos(timing.elapsed().as_nanos()))))
+        ];
+        match self.format {
+            TraceFormat::JsonLines => {
+                let mut separator = "{";
+                for (name, value) in fields.iter() {
+                    write!(self.sink, "{}\"{}\":", separator, name)?;
+                    match value {
+                        // Only op, result, and error_kind are strings
+                        Some(value) if matches!(*name, "op" | "result" | "error_kind")
+                            => write!(self.sink, "\"{}\"", value)?,
+                        Some(value) => write!(self.sink, "{}", value)?,
+                        None => write!(self.sink, "null")?
+                    }
+                    separator = ",";
+                }
+                writeln!(self.sink, "}}")?;
+            },
+            TraceFormat::Csv => {
+                let mut separator = "";
+                for (_, value) in fields.iter() {
+                    write!(self.sink, "{}{}", separator, value.as_deref().unwrap_or(""))?;
+                    separator = ",";
+                }
+                writeln!(self.sink)?;
+            }
+        }
+        self.seq += 1;
+        // An interval of 0 has no remainder, so it never flushes
+        if self.seq.checked_rem(self.flush_interval) == Some(0) {
+            self.sink.flush()?;
+        }
+        Ok(())
+    }
+}
+impl<W: Write> IopCollector for TraceWriter<W> {
+    fn collect_iop(&mut self, iop_record: IopRecord) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(error) = self.write_iop(&iop_record) {
+            self.error = Some(error);
+        }
+    }
+}
+
+fn display<T: Display>(value: Option<T>) -> Option<String> {
+    value.map(|value| value.to_string())
+}
+fn nanos(nanos: u128) -> u64 {
+    u64::try_from(nanos).unwrap_or(u64::MAX)
+}
@@ -0,0 +1,117 @@
+//! A log collector that emits a [`tracing`] event for every operation.
+
+use crate::{IopCollector, IopRecord};
+
+use std::convert::TryFrom;
+
+use tracing::Level;
+
+#[derive(Debug, Clone)]
+/// A log collector that emits every operation as a [`tracing`] event instead of storing it.
+///
+/// Events have the fields `op`, `requested`, `returned`, `pos_before`, `pos_after`, `error_kind`, `injected`, and `elapsed_ns`, plus `label` if one was set; fields that do not apply to an operation are omitted.
+/// Successful operations are emitted at [`Level::TRACE`] and failures at [`Level::WARN`] by default.
+pub struct TracingLog {
+    success_level: Level,
+    failure_level: Level,
+    sample_interval: u64,
+    success_counter: u64,
+    label: Option<String>
+}
+impl Default for TracingLog {
+    fn default() -> TracingLog {
+        TracingLog {
+            success_level: Level::TRACE,
+            failure_level: Level::WARN,
+            sample_interval: 1,
+            success_counter: 0,
+            label: None
+        }
+    }
+}
+impl TracingLog {
+    pub fn new() -> TracingLog {
+        TracingLog::default()
+    }
+    /// Set the level of the events for successful operations.
+    pub fn with_success_level(mut self, level: Level) -> TracingLog {
+        self.success_level = level;
+        self
+    }
+    /// Set the level of the events for failed operations.
+    pub fn with_failure_level(mut self, level: Level) -> TracingLog {
+        self.failure_level = level;
+        self
+    }
+    /// Only emit every `sample_interval`-th successful operation, starting with the first. Failures are always emitted.
+    ///
+    /// # Panics
+    /// Panics if `sample_interval` is 0.
+    pub fn with_sample_interval(mut self, sample_interval: u64) -> TracingLog {
+        assert!(sample_interval > 0, "sample interval must be nonzero");
+        self.sample_interval = sample_interval;
+        self
+    }
+    /// Add a `label` field identifying the I/O object to every event.
+    pub fn with_label<S: Into<String>>(mut self, label: S) -> TracingLog {
+        self.label = Some(label.into());
+        self
+    }
+    pub fn success_level(&self) -> Level {
+        self.success_level
+    }
+    pub fn failure_level(&self) -> Level {
+        self.failure_level
+    }
+    pub fn sample_interval(&self) -> u64 {
+        self.sample_interval
+    }
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    fn emit(&mut self, iop_record: &IopRecord) {
+        let result = iop_record.result();
+        let level = if result.is_ok() {
+            let sampled = self.success_counter.checked_rem(self.sample_interval) == Some(0);
+            self.success_counter += 1;
+            if !sampled {
+                return;
+            }
+            self.success_level
+        } else {
+            self.failure_level
+        };
+        let message = if result.is_ok() { "I/O operation" } else { "I/O operation failed" };
+        // The level of an event must be known at compile time
+        macro_rules! emit_at {
+            ($level:expr) => {
+                tracing::event!($level,
+                    op = ?iop_record.action().kind(),
+                    requested = iop_record.action().requested_len(),
+                    returned = result.returned_len(),
+                    pos_before = iop_record.position_before(),
+                    pos_after = iop_record.position_after(),
+                    error_kind = result.error_kind().map(tracing::field::debug),
+                    injected = iop_record.is_injected(),
+                    elapsed_ns = iop_record.timing().map(|timing| {
+                        u64::try_from(timing.elapsed().as_nanos()).unwrap_or(u64::MAX)
+                    }),
+                    label = self.label.as_deref(),
+                    "{}", message)
+            }
+        }
+        match level {
+            Level::TRACE => emit_at!(Level::TRACE),
+            Level::DEBUG => emit_at!(Level::DEBUG),
+            Level::INFO => emit_at!(Level::INFO),
+            Level::WARN => emit_at!(Level::WARN),
+            Level::ERROR => emit_at!(Level::ERROR)
+        }
+    }
+}
+impl IopCollector for TracingLog {
+    fn collect_iop(&mut self, iop_record: IopRecord) {
+        self.emit(&iop_record);
+    }
+}
@@ -0,0 +1,151 @@
+use io_wrapper_statistics::{IOStatWrapper, IopActions, IopCollector, IopRecord, IopResults, RecordLog, IopKind, PayloadCapture, FaultSchedule, FaultRule, FaultTrigger};
+use io_wrapper_statistics::{BinaryTraceWriter, BinaryTraceReader, BinaryTraceHeader};
+use io_wrapper_statistics::binary_trace::BINARY_TRACE_VERSION;
+
+use std::io::{Read, Write, Seek, SeekFrom, BufRead, Cursor, ErrorKind, IoSlice};
+
+fn assert_same_iop(actual: &IopRecord, expected: &IopRecord) {
+    assert_eq!(actual.action(), expected.action());
+    assert_eq!(actual.result(), expected.result());
+    assert_eq!(actual.timing(), expected.timing());
+    assert_eq!(actual.payload(), expected.payload());
+    assert_eq!(actual.payload_hash(), expected.payload_hash());
+    assert_eq!(actual.is_payload_redacted(), expected.is_payload_redacted());
+    assert_eq!(actual.is_payload_truncated(), expected.is_payload_truncated());
+    assert_eq!(actual.position_before(), expected.position_before());
+    assert_eq!(actual.position_after(), expected.position_after());
+    assert_eq!(actual.is_injected(), expected.is_injected());
+}
+
+#[test]
+fn test_binary_trace_round_trip() {
+    let mut cursor = Cursor::new(b"first\nsecond line\n".to_vec());
+    cursor.set_position(3);
+    let mut io_wrapper = IOStatWrapper::<_, RecordLog>::new(cursor, 3);
+    io_wrapper.enable_timing();
+    io_wrapper.set_payload_capture(PayloadCapture::Full);
+    io_wrapper.set_fault_schedule(FaultSchedule::new(0)
+        .with_rule(FaultRule::interrupted(IopKind::Read, FaultTrigger::NthCall(2))));
+    let mut read_buf: [u8; 4] = [0; 4];
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 4);
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap_err().kind(), ErrorKind::Interrupted);
+    assert_eq!(io_wrapper.seek(SeekFrom::Current(-4)).unwrap(), 3);
+    assert!(io_wrapper.seek(SeekFrom::End(-100)).is_err());
+    let mut line = String::new();
+    assert_eq!(io_wrapper.read_line(&mut line).unwrap(), 3);
+    io_wrapper.set_payload_capture(PayloadCapture::Hashed);
+    assert_eq!(io_wrapper.fill_buf().unwrap().len(), 12);
+    io_wrapper.consume(7);
+    assert_eq!(io_wrapper.write(b"xy").unwrap(), 2);
+    io_wrapper.flush().unwrap();
+
+    let header = BinaryTraceHeader::new(3).with_timestamps(true).with_label("cursor");
+    let mut trace_writer = BinaryTraceWriter::new(Vec::new(), header.clone());
+    for iop_record in io_wrapper.iop_log() {
+        trace_writer.collect_iop(iop_record.clone());
+    }
+    assert_eq!(trace_writer.written_count(), 9);
+    let trace = trace_writer.finish().unwrap();
+
+    let reader = BinaryTraceReader::new(&trace[..]).unwrap();
+    assert_eq!(reader.header(), &header);
+    assert_eq!(reader.header().version(), BINARY_TRACE_VERSION);
+    let read_back: Vec<IopRecord> = reader.collect::<Result<_, _>>().unwrap();
+    assert_eq!(read_back.len(), 9);
+    for (actual, expected) in read_back.iter().zip(io_wrapper.iop_log()) {
+        assert_same_iop(actual, expected);
+    }
+    assert!(read_back[1].is_injected());
+    assert_eq!(read_back[2].position_before(), Some(7));
+    assert_eq!(read_back[2].position_after(), Some(3));
+    assert!(read_back[5].payload_hash().is_some());
+}
+
+#[test]
+fn test_binary_trace_collector_and_errors() {
+    let header = BinaryTraceHeader::new(0);
+    let mut io_wrapper = IOStatWrapper::with_log(Cursor::new(vec![0u8; 1024]), 0,
+        BinaryTraceWriter::new(Vec::new(), header.clone()));
+    io_wrapper.enable_timing();
+    let mut read_buf: [u8; 100] = [0; 100];
+    for _ in 0..10 {
+        assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 100);
+    }
+    let trace = io_wrapper.into_parts().1.finish().unwrap();
+    // A 12 byte header, then per read a tag, the requested and returned lengths, and position deltas of 0 and +100
+    assert_eq!(trace.len(), 12 + 10 * 6);
+    let read_back: Vec<IopRecord> = BinaryTraceReader::new(&trace[..]).unwrap()
+        .collect::<Result<_, _>>().unwrap();
+    // Timing is dropped without timestamps in the header
+    assert!(read_back.iter().all(|iop_record| iop_record.timing().is_none()));
+    assert_eq!(read_back[9].position_after(), Some(1000));
+
+    // A trace without operations still has a header
+    let empty_trace = BinaryTraceWriter::new(Vec::new(), header).finish().unwrap();
+    assert_eq!(BinaryTraceReader::new(&empty_trace[..]).unwrap().count(), 0);
+
+    let mut truncated = BinaryTraceReader::new(&trace[..trace.len() - 1]).unwrap();
+    assert_eq!(truncated.nth(9).unwrap().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    assert!(truncated.next().is_none());
+    assert_eq!(BinaryTraceReader::new(&b"not a trace"[..]).unwrap_err().kind(), ErrorKind::InvalidData);
+    let mut corrupt = trace.clone();
+    corrupt[12] = 0x0f;
+    assert_eq!(BinaryTraceReader::new(&corrupt[..]).unwrap().next().unwrap().unwrap_err().kind(),
+        ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_binary_trace_error_kinds() {
+    let error_kinds = [ErrorKind::NotFound, ErrorKind::Other, ErrorKind::NotADirectory,
+        ErrorKind::ArgumentListTooLong, ErrorKind::CrossesDevices, ErrorKind::InvalidFilename];
+    let mut trace_writer = BinaryTraceWriter::new(Vec::new(), BinaryTraceHeader::new(0));
+    for error_kind in error_kinds.iter() {
+        trace_writer.collect_iop(IopRecord::new(IopActions::Flush, IopResults::Flush(Err(*error_kind))));
+    }
+    let trace = trace_writer.finish().unwrap();
+    let read_back: Vec<IopResults> = BinaryTraceReader::new(&trace[..]).unwrap()
+        .map(|iop_record| iop_record.unwrap().result())
+        .collect();
+    let expected: Vec<IopResults> = error_kinds.iter()
+        .map(|error_kind| IopResults::Flush(Err(*error_kind)))
+        .collect();
+    assert_eq!(read_back, expected);
+}
+
+#[test]
+fn test_binary_trace_payload_flags() {
+    let mut io_wrapper = IOStatWrapper::<_, RecordLog>::new(Cursor::new(Vec::new()), 0);
+    io_wrapper.set_payload_capture(PayloadCapture::Prefix(4));
+    io_wrapper.write_all(b"token=1234").unwrap();
+    io_wrapper.set_payload_redactor(|_action, payload| payload.fill(b'*'));
+    io_wrapper.write_all(b"abc").unwrap();
+
+    let mut trace_writer = BinaryTraceWriter::new(Vec::new(), BinaryTraceHeader::new(0));
+    for iop_record in io_wrapper.iop_log() {
+        trace_writer.collect_iop(iop_record.clone());
+    }
+    let trace = trace_writer.finish().unwrap();
+    let read_back: Vec<IopRecord> = BinaryTraceReader::new(&trace[..]).unwrap()
+        .collect::<Result<_, _>>().unwrap();
+    for (actual, expected) in read_back.iter().zip(io_wrapper.iop_log()) {
+        assert_same_iop(actual, expected);
+    }
+    assert!(read_back[0].is_payload_truncated() && !read_back[0].is_payload_redacted());
+    assert!(!read_back[1].is_payload_truncated() && read_back[1].is_payload_redacted());
+}
+
+#[test]
+fn test_binary_trace_write_vectored_slices() {
+    let mut io_wrapper = IOStatWrapper::<_, RecordLog>::new(Cursor::new(Vec::new()), 0);
+    let slices = [IoSlice::new(b"ab"), IoSlice::new(b""), IoSlice::new(b"cdefg")];
+    assert_eq!(io_wrapper.write_vectored(&slices).unwrap(), 7);
+
+    let mut trace_writer = BinaryTraceWriter::new(Vec::new(), BinaryTraceHeader::new(0));
+    trace_writer.collect_iop(io_wrapper.iop_log()[0].clone());
+    let trace = trace_writer.finish().unwrap();
+    let read_back: Vec<IopRecord> = BinaryTraceReader::new(&trace[..]).unwrap()
+        .collect::<Result<_, _>>().unwrap();
+    assert_same_iop(&read_back[0], &io_wrapper.iop_log()[0]);
+    assert_eq!(read_back[0].action(), &IopActions::WriteVectored(vec![2, 0, 5]));
+    assert_eq!(read_back[0].action().requested_len(), Some(7));
+}
@@ -0,0 +1,60 @@
+use io_wrapper_statistics::{IOStatWrapper, IopRecord, RecordLog, IopActions, IopResults, IopTiming, ChromeTraceExporter};
+
+use std::io::{Read, Write, Seek, SeekFrom, Cursor};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_chrome_trace_events() {
+    let log = vec![
+        IopRecord::new(IopActions::Read(16), IopResults::Read(Ok(8)))
+            .with_position(0, 8)
+            .with_timing(IopTiming::new(Duration::from_nanos(1500), Duration::from_nanos(250))),
+        IopRecord::new(IopActions::Flush, IopResults::Flush(Err(std::io::ErrorKind::BrokenPipe)))
+            .with_timing(IopTiming::new(Duration::from_micros(3), Duration::from_nanos(7)))
+            .mark_injected(),
+        IopRecord::new(IopActions::Write(4), IopResults::Write(Ok(4)))
+    ];
+    let exporter = ChromeTraceExporter::new()
+        .with_process_name("test")
+        .with_track("disk \"A\"", &log);
+    assert_eq!(exporter.track_count(), 1);
+    assert_eq!(exporter.event_count(), 2);
+    assert_eq!(exporter.skipped_count(), 1);
+    assert_eq!(exporter.to_json(), concat!(
+        "{\"traceEvents\":[\n",
+        r#"{"name":"process_name","ph":"M","pid":1,"tid":0,"args":{"name":"test"}},"#, "\n",
+        r#"{"name":"thread_name","ph":"M","pid":1,"tid":1,"args":{"name":"disk \"A\""}},"#, "\n",
+        r#"{"name":"Read","cat":"io","ph":"X","ts":1.500,"dur":0.250,"pid":1,"tid":1,"args":{"requested":16,"returned":8,"pos_before":0,"pos_after":8}},"#, "\n",
+        r#"{"name":"Flush","cat":"io","ph":"X","ts":3.000,"dur":0.007,"pid":1,"tid":1,"args":{"error_kind":"BrokenPipe","injected":true}}"#, "\n",
+        "],\"displayTimeUnit\":\"ns\"}\n"
+    ));
+}
+
+#[test]
+fn test_chrome_trace_multiple_wrappers() {
+    let epoch = Instant::now();
+    let mut reader = IOStatWrapper::<_, RecordLog>::new(Cursor::new(vec![0u8; 32]), 0);
+    let mut writer = IOStatWrapper::<_, RecordLog>::new(Cursor::new(Vec::new()), 0);
+    reader.enable_timing_with_epoch(epoch);
+    writer.enable_timing_with_epoch(epoch);
+    let mut buf: [u8; 16] = [0; 16];
+    assert_eq!(reader.read(&mut buf).unwrap(), 16);
+    assert_eq!(writer.write(&buf).unwrap(), 16);
+    writer.flush().unwrap();
+    assert_eq!(reader.seek(SeekFrom::Start(0)).unwrap(), 0);
+
+    let exporter = ChromeTraceExporter::new()
+        .with_track("reader", reader.iop_log())
+        .with_track("writer", writer.iop_log());
+    assert_eq!(exporter.track_count(), 2);
+    assert_eq!(exporter.event_count(), 4);
+    let mut json = Vec::new();
+    exporter.write_json(&mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed["traceEvents"].as_array().unwrap().len(), 6);
+    assert!(json.contains(r#""name":"thread_name","ph":"M","pid":1,"tid":2,"args":{"name":"writer"}"#));
+    assert_eq!(json.matches(r#""ph":"X""#).count(), 4);
+    assert_eq!(json.matches(r#""tid":1,"args":{"requested""#).count(), 1);
+    assert_eq!(json.matches(r#""name":"Seek","cat":"io","ph":"X""#).count(), 1);
+}
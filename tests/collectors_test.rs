@@ -0,0 +1,69 @@
+use io_wrapper_statistics::{IOStatWrapper, IopActions, IopKind, NoLog, OpSummary, PayloadCapture, RingLog};
+
+use std::io::{Read, Seek, SeekFrom, Cursor, ErrorKind};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn test_ring_log() {
+    let mut io_wrapper = IOStatWrapper::<_, RingLog<3>>::new(Cursor::new(vec![0; 16]), 0);
+    for read_len in 1..=5 {
+        let mut read_buf = vec![0; read_len];
+        assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), read_len);
+    }
+
+    let iop_log = io_wrapper.iop_log();
+    assert_eq!(iop_log.len(), 3);
+    assert_eq!(iop_log.dropped_count(), 2);
+    assert_eq!(iop_log.total_count(), 5);
+    let kept_lens: Vec<Option<usize>> = iop_log.iter()
+        .map(|(action, _)| action.requested_len())
+        .collect();
+    assert_eq!(kept_lens, [Some(3), Some(4), Some(5)]);
+    assert!(matches!(iop_log.get(0).unwrap().0, IopActions::Read(3)));
+    assert_eq!(io_wrapper.read_call_counter().success_ctr(), 5);
+}
+
+#[test]
+fn test_no_log_counts() {
+    let redacted_count = Arc::new(AtomicUsize::new(0));
+    let redactor_count = Arc::clone(&redacted_count);
+    let mut io_wrapper = IOStatWrapper::<_, NoLog>::new(Cursor::new(vec![0; 16]), 0);
+    io_wrapper.set_payload_capture(PayloadCapture::Full);
+    io_wrapper.set_payload_redactor(move |_, _| {
+        redactor_count.fetch_add(1, Ordering::Relaxed);
+    });
+    let mut read_buf: [u8; 8] = [0; 8];
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 8);
+    assert_eq!(io_wrapper.read(&mut read_buf[..4]).unwrap(), 4);
+    io_wrapper.seek(SeekFrom::Start(2)).unwrap();
+
+    assert_eq!(io_wrapper.read_call_counter().success_ctr(), 2);
+    assert_eq!(io_wrapper.read_byte_counter(), 12);
+    assert_eq!(io_wrapper.read_requested_byte_counter(), 12);
+    assert_eq!(io_wrapper.seek_distance_counter(), 10);
+    // No payload was captured, so there was nothing to redact
+    assert_eq!(redacted_count.load(Ordering::Relaxed), 0);
+}
+
+#[test]
+fn test_op_summary() {
+    let mut io_wrapper = IOStatWrapper::<_, OpSummary>::new(Cursor::new(vec![0; 16]), 0);
+    let mut read_buf: [u8; 8] = [0; 8];
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 8);
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 8);
+    io_wrapper.seek(SeekFrom::Start(4)).unwrap();
+    assert_eq!(io_wrapper.seek(SeekFrom::Current(-8)).unwrap_err().kind(),
+        ErrorKind::InvalidInput);
+
+    let summary = io_wrapper.iop_log();
+    let read_summary = summary.get(IopKind::Read).unwrap();
+    assert_eq!(read_summary.call_counter().success_ctr(), 2);
+    assert_eq!(read_summary.returned_byte_counter(), 16);
+    let seek_summary = summary.get(IopKind::Seek).unwrap();
+    assert_eq!(seek_summary.call_counter().success_ctr(), 1);
+    assert_eq!(seek_summary.call_counter().failure_ctr(), 1);
+    assert_eq!(seek_summary.failure_counter(ErrorKind::InvalidInput), 1);
+    assert_eq!(summary.failure_counter(ErrorKind::InvalidInput), 1);
+    assert!(summary.get(IopKind::Write).is_none());
+}
@@ -0,0 +1,99 @@
+use io_wrapper_statistics::{IOStatWrapper, IopInfoPair, RecordLog, IopKind, FaultSchedule, FaultRule, FaultTrigger, SplitPolicy};
+
+use std::io::{Read, Write, Seek, SeekFrom, Cursor, ErrorKind};
+
+#[test]
+fn test_fault_nth_call_and_byte_offset() {
+    let mut io_wrapper = IOStatWrapper::<_, RecordLog>::new(Cursor::new(vec![0u8; 64]), 0);
+    io_wrapper.set_fault_schedule(FaultSchedule::new(0)
+        .with_rule(FaultRule::new(IopKind::Read, FaultTrigger::NthCall(2), ErrorKind::Other))
+        .with_rule(FaultRule::new(IopKind::Read, FaultTrigger::ByteOffset(40), ErrorKind::InvalidData))
+        .with_rule(FaultRule::new(IopKind::Flush, FaultTrigger::NthCall(1), ErrorKind::BrokenPipe)));
+    let mut read_buf: [u8; 16] = [0; 16];
+
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 16);
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap_err().kind(), ErrorKind::Other);
+    // The injected failure did not reach the cursor
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 16);
+    assert_eq!(io_wrapper.seek_pos(), 32);
+    // Bytes 32..48 contain the bad offset, while 41..57 does not
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap_err().kind(), ErrorKind::InvalidData);
+    io_wrapper.seek(SeekFrom::Start(41)).unwrap();
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 16);
+    assert_eq!(io_wrapper.flush().unwrap_err().kind(), ErrorKind::BrokenPipe);
+    io_wrapper.flush().unwrap();
+
+    assert_eq!(io_wrapper.fault_schedule().unwrap().injected_counter(), 3);
+    assert_eq!(io_wrapper.read_call_counter().success_ctr(), 3);
+    assert_eq!(io_wrapper.read_call_counter().failure_ctr(), 2);
+    assert_eq!(io_wrapper.read_byte_counter(), 48);
+    let injected_flags: Vec<bool> = io_wrapper.iop_log().iter()
+        .map(|iop_record| iop_record.is_injected())
+        .collect();
+    assert_eq!(injected_flags, [false, true, false, true, false, false, true, false]);
+}
+
+#[test]
+fn test_fault_probability_is_reproducible() {
+    fn failure_pattern(seed: u64) -> Vec<bool> {
+        let mut io_wrapper = IOStatWrapper::<_, Vec<IopInfoPair>>::new(Cursor::new(Vec::new()), 0);
+        io_wrapper.set_fault_schedule(FaultSchedule::new(seed)
+            .with_rule(FaultRule::new(IopKind::Write, FaultTrigger::Probability(0.5), ErrorKind::Other)));
+        (0..64).map(|_| io_wrapper.write(&[0]).is_err()).collect()
+    }
+    let pattern = failure_pattern(42);
+    assert_eq!(pattern, failure_pattern(42));
+    assert!(pattern.contains(&true) && pattern.contains(&false));
+    assert_ne!(pattern, failure_pattern(43));
+}
+
+#[test]
+fn test_split_policies_keep_counters_exact() {
+    let data: Vec<u8> = (0..100).collect();
+    let mut io_wrapper = IOStatWrapper::<_, Vec<IopInfoPair>>::new(Cursor::new(data.clone()), 0);
+    io_wrapper.set_read_split_policy(SplitPolicy::FixedChunk(7));
+    let mut read_buf: [u8; 16] = [0; 16];
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 7);
+    assert_eq!(io_wrapper.iop_log()[0].0.requested_len(), Some(16));
+
+    io_wrapper.set_read_split_policy(SplitPolicy::RandomSplit(5));
+    let mut read_back = read_buf[..7].to_vec();
+    io_wrapper.read_to_end(&mut read_back).unwrap();
+    assert_eq!(read_back, data);
+    assert_eq!(io_wrapper.read_byte_counter(), 100);
+    assert_eq!(io_wrapper.seek_pos(), 100);
+    assert!(io_wrapper.read_call_counter().success_ctr() > 3);
+
+    let mut io_wrapper = IOStatWrapper::<_, Vec<IopInfoPair>>::new(Cursor::new(Vec::new()), 0);
+    io_wrapper.set_write_split_policy(SplitPolicy::OneByte);
+    io_wrapper.write_all(&data[..10]).unwrap();
+    assert_eq!(io_wrapper.write_call_counter().success_ctr(), 10);
+    assert_eq!(io_wrapper.write_byte_counter(), 10);
+    assert_eq!(io_wrapper.seek_pos(), 10);
+    assert_eq!(io_wrapper.into_inner().into_inner(), &data[..10]);
+}
+
+#[test]
+fn test_retry_statistics() {
+    let mut io_wrapper = IOStatWrapper::<_, Vec<IopInfoPair>>::new(Cursor::new(vec![0u8; 32]), 0);
+    io_wrapper.set_fault_schedule(FaultSchedule::new(0)
+        .with_rule(FaultRule::interrupted(IopKind::Read, FaultTrigger::NthCall(1)))
+        .with_rule(FaultRule::would_block(IopKind::Read, FaultTrigger::NthCall(2)))
+        .with_rule(FaultRule::interrupted(IopKind::Write, FaultTrigger::NthCall(1)))
+        .with_rule(FaultRule::new(IopKind::Seek, FaultTrigger::NthCall(1), ErrorKind::InvalidInput)));
+
+    // read_exact retries on Interrupted but returns WouldBlock to the caller, which then retries
+    let mut read_buf: [u8; 8] = [0; 8];
+    assert_eq!(io_wrapper.read_exact(&mut read_buf).unwrap_err().kind(), ErrorKind::WouldBlock);
+    io_wrapper.read_exact(&mut read_buf).unwrap();
+    // A caller that propagates Interrupted instead of retrying
+    assert_eq!(io_wrapper.write(&[1]).unwrap_err().kind(), ErrorKind::Interrupted);
+    assert_eq!(io_wrapper.seek(SeekFrom::Start(0)).unwrap_err().kind(), ErrorKind::InvalidInput);
+
+    let retry_statistics = io_wrapper.retry_statistics();
+    assert_eq!(retry_statistics.retryable_failure_counter(), 3);
+    assert_eq!(retry_statistics.fatal_failure_counter(), 1);
+    assert_eq!(retry_statistics.retry_counter(), 2);
+    assert_eq!(retry_statistics.max_consecutive_retries(), 2);
+    assert_eq!(retry_statistics.interrupted_give_up_counter(), 1);
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "futures-io")]
+
+use io_wrapper_statistics::{IOStatWrapper, IopInfoPair, IopKind};
+
+use std::io::SeekFrom;
+
+use futures::executor::block_on;
+use futures::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, AsyncWriteExt, Cursor};
+
+#[test]
+fn test_futures_read_bufread() {
+    block_on(async {
+        let base_io_obj = Cursor::new(b"first\nsecond\nthird".to_vec());
+        let mut io_wrapper = IOStatWrapper::<_,Vec<IopInfoPair>>::new(base_io_obj, 0);
+        let mut read_buf: [u8; 6] = [0; 6];
+
+        io_wrapper.read_exact(&mut read_buf).await.unwrap();
+        assert_eq!(&read_buf, b"first\n");
+        assert_eq!(io_wrapper.fill_buf().await.unwrap(), b"second\nthird");
+        io_wrapper.consume_unpin(7);
+        let mut rest = String::new();
+        assert_eq!(io_wrapper.read_to_string(&mut rest).await.unwrap(), 5);
+        assert_eq!(rest, "third");
+
+        assert_eq!(io_wrapper.read_byte_counter(), 18);
+        assert_eq!(io_wrapper.seek_pos(), 18);
+        assert_eq!(io_wrapper.fill_buf_call_counter().success_ctr(), 1);
+        assert_eq!(io_wrapper.pending_counter(IopKind::Read), 0);
+        assert_eq!(io_wrapper.iop_log()[1].0.kind(), IopKind::FillBuf);
+        assert_eq!(io_wrapper.iop_log()[2].0.kind(), IopKind::Consume);
+    });
+}
+
+#[test]
+fn test_futures_write_seek() {
+    block_on(async {
+        let mut io_wrapper = IOStatWrapper::<_,Vec<IopInfoPair>>::new(Cursor::new(Vec::new()), 0);
+
+        io_wrapper.write_all(b"abcdef").await.unwrap();
+        assert_eq!(io_wrapper.seek(SeekFrom::Start(2)).await.unwrap(), 2);
+        io_wrapper.write_all(b"CD").await.unwrap();
+        io_wrapper.flush().await.unwrap();
+        io_wrapper.close().await.unwrap();
+
+        assert_eq!(io_wrapper.write_call_counter().success_ctr(), 2);
+        assert_eq!(io_wrapper.write_byte_counter(), 8);
+        assert_eq!(io_wrapper.seek_call_counter().success_ctr(), 1);
+        assert_eq!(io_wrapper.seek_pos(), 4);
+        assert_eq!(io_wrapper.write_flush_counter().success_ctr(), 1);
+        assert_eq!(io_wrapper.write_shutdown_counter().success_ctr(), 1);
+        assert_eq!(io_wrapper.into_inner().into_inner(), b"abCDef");
+    });
+}
@@ -0,0 +1,42 @@
+use io_wrapper_statistics::{IOStatWrapper, IopHistograms, IopKind, LogLinearHistogram};
+
+use std::io::{Read, Cursor};
+
+#[test]
+fn test_histogram_quantiles() {
+    let mut histogram = LogLinearHistogram::new();
+    assert_eq!(histogram.p50(), None);
+    for value in 1..=1000 {
+        histogram.record(value);
+    }
+
+    assert_eq!(histogram.count(), 1000);
+    assert_eq!(histogram.min(), Some(1));
+    assert_eq!(histogram.max(), Some(1000));
+    assert_eq!(histogram.mean(), Some(500.5));
+    for &(quantile, exact) in &[(0.5, 500.0), (0.9, 900.0), (0.99, 990.0)] {
+        let estimate = histogram.value_at_quantile(quantile).unwrap() as f64;
+        assert!((estimate - exact).abs() / exact < 0.02,
+            "p{} was {}", quantile * 100.0, estimate);
+    }
+    assert_eq!(histogram.value_at_quantile(1.0), Some(1000));
+}
+
+#[test]
+fn test_histogram_collector() {
+    let mut io_wrapper = IOStatWrapper::<_, IopHistograms>::new(Cursor::new(vec![0; 100]), 0);
+    io_wrapper.enable_timing();
+    let mut slice_buf: [u8; 64] = [0; 64];
+    assert_eq!(io_wrapper.read(&mut slice_buf).unwrap(), 64);
+    assert_eq!(io_wrapper.read(&mut slice_buf).unwrap(), 36);
+    assert_eq!(io_wrapper.read(&mut slice_buf).unwrap(), 0);
+
+    let read_hists = io_wrapper.iop_log().get(IopKind::Read).unwrap();
+    assert_eq!(read_hists.requested_len().count(), 3);
+    assert_eq!(read_hists.requested_len().min(), Some(64));
+    assert_eq!(read_hists.returned_len().min(), Some(0));
+    assert_eq!(read_hists.returned_len().max(), Some(64));
+    assert_eq!(read_hists.returned_len().p50(), Some(36));
+    assert_eq!(read_hists.latency_nanos().count(), 3);
+    assert!(io_wrapper.iop_log().get(IopKind::Seek).is_none());
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "metrics")]
+
+use io_wrapper_statistics::{IOStatWrapper, IopInfoPair, PrometheusRenderer};
+
+use std::io::{Read, Write, Seek, SeekFrom, BufReader, Cursor};
+
+#[test]
+fn test_prometheus_renderer() {
+    let mut reader = IOStatWrapper::<_, Vec<IopInfoPair>>::new(Cursor::new(vec![0u8; 32]), 0);
+    let mut writer = IOStatWrapper::<_, Vec<IopInfoPair>>::new(Cursor::new(Vec::new()), 0);
+    let mut idle = IOStatWrapper::<_, Vec<IopInfoPair>>::new(Cursor::new(Vec::<u8>::new()), 0);
+    let renderer = PrometheusRenderer::new()
+        .with_stats("input", reader.shared_stats())
+        .with_stats("out \"1\"", writer.shared_stats())
+        .with_stats("idle", idle.shared_stats());
+    assert_eq!(renderer.wrapper_count(), 3);
+
+    let mut buf: [u8; 20] = [0; 20];
+    assert_eq!(reader.read(&mut buf).unwrap(), 20);
+    assert_eq!(reader.read(&mut buf).unwrap(), 12);
+    assert!(reader.seek(SeekFrom::Current(-100)).is_err());
+    assert_eq!(writer.write(&buf[..5]).unwrap(), 5);
+    writer.flush().unwrap();
+
+    let text = renderer.render();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 6 * 2 + 4 * 6 + 2 * 3);
+    assert_eq!(&lines[..8], [
+        "# HELP io_read_calls_total Number of read operations.",
+        "# TYPE io_read_calls_total counter",
+        "io_read_calls_total{wrapper=\"input\",result=\"success\"} 2",
+        "io_read_calls_total{wrapper=\"input\",result=\"failure\"} 0",
+        "io_read_calls_total{wrapper=\"out \\\"1\\\"\",result=\"success\"} 0",
+        "io_read_calls_total{wrapper=\"out \\\"1\\\"\",result=\"failure\"} 0",
+        "io_read_calls_total{wrapper=\"idle\",result=\"success\"} 0",
+        "io_read_calls_total{wrapper=\"idle\",result=\"failure\"} 0"
+    ]);
+    assert!(lines.contains(&"io_read_bytes_total{wrapper=\"input\"} 32"));
+    assert!(lines.contains(&"io_write_bytes_total{wrapper=\"out \\\"1\\\"\"} 5"));
+    assert!(lines.contains(&"io_seek_calls_total{wrapper=\"input\",result=\"failure\"} 1"));
+    assert!(lines.contains(&"io_flush_calls_total{wrapper=\"out \\\"1\\\"\",result=\"success\"} 1"));
+
+    // The renderer reads live values
+    assert_eq!(writer.write(&buf[..3]).unwrap(), 3);
+    assert!(renderer.render().contains("io_write_bytes_total{wrapper=\"out \\\"1\\\"\"} 8\n"));
+}
+
+#[test]
+#[should_panic(expected = "PrometheusRenderer already has a wrapper labeled \"cursor\"")]
+fn test_prometheus_renderer_duplicate_label_panics() {
+    let mut first = IOStatWrapper::<_, Vec<IopInfoPair>>::new(Cursor::new(Vec::<u8>::new()), 0);
+    let mut second = IOStatWrapper::<_, Vec<IopInfoPair>>::new(Cursor::new(Vec::<u8>::new()), 0);
+    PrometheusRenderer::new()
+        .with_stats("cursor", first.shared_stats())
+        .with_stats("cursor", second.shared_stats());
+}
+
+#[test]
+fn test_record_metrics() {
+    use io_wrapper_statistics::metrics_export::record_metrics;
+
+    use metrics::{Counter, CounterFn, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+
+    use std::collections::BTreeMap;
+    use std::sync::{Arc, Mutex};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// A recorder that keeps counters keyed by their name and sorted labels.
+    #[derive(Default)]
+    struct TestRecorder {
+        counters: Mutex<BTreeMap<String, Arc<TestCounter>>>
+    }
+    #[derive(Default)]
+    struct TestCounter(AtomicU64);
+    impl CounterFn for TestCounter {
+        fn increment(&self, value: u64) {
+            self.0.fetch_add(value, Ordering::Relaxed);
+        }
+        fn absolute(&self, value: u64) {
+            self.0.fetch_max(value, Ordering::Relaxed);
+        }
+    }
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+        fn describe_gauge(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+        fn describe_histogram(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+        fn register_counter(&self, key: &Key, _metadata: &Metadata<'_>) -> Counter {
+            let labels: Vec<String> = key.labels()
+                .map(|label| format!("{}={}", label.key(), label.value()))
+                .collect();
+            let name = format!("{}{{{}}}", key.name(), labels.join(","));
+            let counter = self.counters.lock().unwrap().entry(name).or_default().clone();
+            Counter::from_arc(counter)
+        }
+        fn register_gauge(&self, _key: &Key, _metadata: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+        fn register_histogram(&self, _key: &Key, _metadata: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    let mut io_wrapper = IOStatWrapper::<_, Vec<IopInfoPair>>::new(Cursor::new(vec![0u8; 32]), 0);
+    let shared_stats = io_wrapper.shared_stats();
+    let mut buf: [u8; 20] = [0; 20];
+    assert_eq!(io_wrapper.read(&mut buf).unwrap(), 20);
+    assert!(io_wrapper.seek(SeekFrom::Current(-100)).is_err());
+
+    let recorder = TestRecorder::default();
+    metrics::with_local_recorder(&recorder, || {
+        record_metrics("cursor", &shared_stats);
+        // The wrapper can be moved elsewhere, e.g. into a reader or another thread
+        let mut reader = BufReader::new(io_wrapper);
+        assert_eq!(reader.read(&mut buf).unwrap(), 12);
+        record_metrics("cursor", &shared_stats);
+    });
+    let counters = recorder.counters.lock().unwrap();
+    let value = |name: &str| counters[name].0.load(Ordering::Relaxed);
+    assert_eq!(counters.len(), 10);
+    assert_eq!(value("io_read_calls_total{wrapper=cursor,result=success}"), 2);
+    assert_eq!(value("io_read_bytes_total{wrapper=cursor}"), 32);
+    assert_eq!(value("io_seek_calls_total{wrapper=cursor,result=failure}"), 1);
+    assert_eq!(value("io_write_bytes_total{wrapper=cursor}"), 0);
+}
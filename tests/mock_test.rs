@@ -0,0 +1,52 @@
+use io_wrapper_statistics::{MockIo, IopActions, IopResults};
+
+use std::io::{Read, Write, Seek, SeekFrom, ErrorKind};
+
+#[test]
+fn test_mock_serves_expectations() {
+    let mut mock_io = MockIo::new()
+        .expect(IopActions::Read(4096), IopResults::Read(Ok(12)))
+        .expect_read(8, b"abc")
+        .expect(IopActions::Seek(SeekFrom::Start(0)), IopResults::Seek(Err(ErrorKind::InvalidInput)))
+        .expect_write(b"reply")
+        .expect(IopActions::Flush, IopResults::Flush(Ok(())));
+
+    let mut read_buf = [1u8; 4096];
+    assert_eq!(mock_io.read(&mut read_buf).unwrap(), 12);
+    assert_eq!(read_buf[..12], [0; 12]);
+    assert_eq!(read_buf[12], 1);
+    assert_eq!(mock_io.read(&mut read_buf[..8]).unwrap(), 3);
+    assert_eq!(&read_buf[..3], b"abc");
+    assert_eq!(mock_io.seek(SeekFrom::Start(0)).unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert_eq!(mock_io.remaining(), 2);
+    mock_io.write_all(b"reply").unwrap();
+    mock_io.flush().unwrap();
+    assert_eq!(mock_io.performed_count(), 5);
+    mock_io.verify();
+}
+
+#[test]
+#[should_panic(expected = "mock diverged at operation 1: expected Flush, got Seek(Start(0)) (expected result Flush(Ok(())), previous operation Some(Write(2))")]
+fn test_mock_reports_mismatch() {
+    let mut mock_io = MockIo::new()
+        .expect_write(b"hi")
+        .expect(IopActions::Flush, IopResults::Flush(Ok(())));
+    mock_io.write_all(b"hi").unwrap();
+    let _ = mock_io.seek(SeekFrom::Start(0));
+}
+
+#[test]
+#[should_panic(expected = "mock expected 1 more operations after operation 0: [(Flush, Flush(Ok(())))]")]
+fn test_mock_verify_reports_missing() {
+    MockIo::new()
+        .expect(IopActions::Flush, IopResults::Flush(Ok(())))
+        .verify();
+}
+
+#[test]
+#[should_panic(expected = "mock expectation 1 is a read of 4 bytes returning 5 bytes of data")]
+fn test_mock_rejects_oversized_read_data() {
+    MockIo::new()
+        .expect_read(4, b"abcd")
+        .expect_read(4, b"efghi");
+}
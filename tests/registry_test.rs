@@ -0,0 +1,64 @@
+use io_wrapper_statistics::{IOStatWrapper, IopInfoPair, InstrumentReport, registry};
+
+use std::io::{Read, Write, Cursor};
+
+fn find(instruments: &[InstrumentReport], id: u64) -> Option<&InstrumentReport> {
+    instruments.iter().find(|instrument| instrument.id() == id)
+}
+
+#[test]
+fn test_registry_live_and_dropped() {
+    assert_eq!(registry::dropped_capacity(), 16);
+    let mut reader = IOStatWrapper::<_, Vec<IopInfoPair>>::new(Cursor::new(vec![0u8; 32]), 0);
+    let mut writer = IOStatWrapper::<_, Vec<IopInfoPair>>::new(Cursor::new(Vec::new()), 0);
+    reader.set_name("reader");
+    assert_eq!(reader.registry_id(), None);
+    let reader_id = reader.register();
+    assert_eq!(reader.register(), reader_id);
+    assert_eq!(reader.registry_id(), Some(reader_id));
+    let writer_id = writer.register();
+    assert_ne!(reader_id, writer_id);
+    // Names can be set after registering
+    writer.set_name("writer");
+    assert_eq!(writer.name(), Some("writer"));
+
+    let mut buf: [u8; 20] = [0; 20];
+    assert_eq!(reader.read(&mut buf).unwrap(), 20);
+    assert_eq!(writer.write(&buf[..7]).unwrap(), 7);
+
+    let live = registry::live_instruments();
+    let reader_report = find(&live, reader_id).unwrap();
+    assert!(reader_report.is_live());
+    assert_eq!(reader_report.name(), Some("reader"));
+    assert_eq!(reader_report.snapshot().read_byte_counter(), 20);
+    assert_eq!(find(&live, writer_id).unwrap().name(), Some("writer"));
+
+    // A consumed wrapper is no longer live, and its final counters are kept
+    assert_eq!(writer.write(&buf[..3]).unwrap(), 3);
+    let (cursor, _) = writer.into_parts();
+    assert_eq!(cursor.into_inner().len(), 10);
+    assert!(find(&registry::live_instruments(), writer_id).is_none());
+    let dropped = registry::dropped_instruments();
+    let writer_report = find(&dropped, writer_id).unwrap();
+    assert!(!writer_report.is_live());
+    assert_eq!(writer_report.snapshot().write_byte_counter(), 10);
+    assert_eq!(writer_report.snapshot().write_call_counter().success_ctr(), 2);
+    let instruments = registry::instruments();
+    assert!(find(&instruments, reader_id).unwrap().is_live());
+    assert!(!find(&instruments, writer_id).unwrap().is_live());
+
+    drop(reader);
+    let dropped = registry::dropped_instruments();
+    assert_eq!(find(&dropped, reader_id).unwrap().snapshot().read_byte_counter(), 20);
+
+    registry::set_dropped_capacity(1);
+    let dropped = registry::dropped_instruments();
+    assert_eq!(dropped.len(), 1);
+    assert_eq!(dropped[0].id(), reader_id);
+    registry::set_dropped_capacity(0);
+    let mut temporary = IOStatWrapper::<_, Vec<IopInfoPair>>::new(Cursor::new(Vec::<u8>::new()), 0);
+    temporary.register();
+    drop(temporary);
+    assert!(registry::dropped_instruments().is_empty());
+    registry::set_dropped_capacity(16);
+}
@@ -0,0 +1,182 @@
+use io_wrapper_statistics::{IOStatWrapper, IopActions, IopRecord, IopResults, RecordLog, IopKind, PayloadCapture, ReplayIo, FaultSchedule, FaultRule, FaultTrigger, SplitPolicy};
+
+use std::io::{Read, BufRead, Write, Seek, SeekFrom, BufReader, Cursor};
+
+fn run_workload<T: Read + Write + Seek>(io_obj: &mut T) -> Vec<u8> {
+    let mut header: [u8; 4] = [0; 4];
+    io_obj.read_exact(&mut header).unwrap();
+    io_obj.seek(SeekFrom::Start(10)).unwrap();
+    let mut body = Vec::new();
+    io_obj.read_to_end(&mut body).unwrap();
+    io_obj.write_all(b"trailer").unwrap();
+    io_obj.flush().unwrap();
+    header.iter().chain(body.iter()).copied().collect()
+}
+
+#[test]
+fn test_replay_matches_recording() {
+    let data: Vec<u8> = (0..40).collect();
+    let mut io_wrapper = IOStatWrapper::<_, RecordLog>::new(Cursor::new(data), 0);
+    io_wrapper.set_payload_capture(PayloadCapture::Full);
+    io_wrapper.set_read_split_policy(SplitPolicy::RandomSplit(7));
+    io_wrapper.set_fault_schedule(FaultSchedule::new(0)
+        .with_rule(FaultRule::interrupted(IopKind::Read, FaultTrigger::NthCall(2))));
+    let recorded_output = run_workload(&mut io_wrapper);
+
+    let mut replay_io = ReplayIo::new(io_wrapper.iop_log().clone());
+    assert_eq!(run_workload(&mut replay_io), recorded_output);
+    assert!(replay_io.is_finished());
+    assert_eq!(replay_io.replayed_count(), io_wrapper.iop_log().len());
+}
+
+#[test]
+fn test_replay_bufread() {
+    let mut io_wrapper = IOStatWrapper::<_, RecordLog>::new(
+        BufReader::with_capacity(8, Cursor::new(b"first line\nsecond\n".to_vec())), 0);
+    io_wrapper.set_payload_capture(PayloadCapture::Full);
+    let mut line = String::new();
+    io_wrapper.read_line(&mut line).unwrap();
+    let available = io_wrapper.fill_buf().unwrap().len();
+    io_wrapper.consume(available);
+
+    let mut replay_io = ReplayIo::new(io_wrapper.iop_log().clone());
+    let mut replay_line = String::new();
+    assert_eq!(replay_io.read_line(&mut replay_line).unwrap(), 11);
+    assert_eq!(replay_line, line);
+    // The rest of the second 8-byte chunk of the inner buffer
+    assert_eq!(replay_io.fill_buf().unwrap(), b"secon");
+    replay_io.consume(available);
+    assert!(replay_io.is_finished());
+}
+
+#[test]
+#[should_panic(expected = "replay diverged at operation 1: expected Write(3), got Write(4)")]
+fn test_replay_divergence_panics() {
+    let mut io_wrapper = IOStatWrapper::<_, RecordLog>::new(Cursor::new(Vec::new()), 0);
+    io_wrapper.set_payload_capture(PayloadCapture::Full);
+    io_wrapper.write_all(b"abc").unwrap();
+    io_wrapper.write_all(b"def").unwrap();
+
+    let mut replay_io = ReplayIo::new(io_wrapper.iop_log().clone());
+    replay_io.write_all(b"abc").unwrap();
+    replay_io.write_all(b"defg").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "replay operation 0 has mismatched result Write(Ok(4)) for Read(4)")]
+fn test_replay_mismatched_result_panics() {
+    let mut replay_io = ReplayIo::new(vec![IopRecord::new(IopActions::Read(4), IopResults::Write(Ok(4)))]);
+    let _ = replay_io.read(&mut [0; 4]);
+}
+
+#[test]
+fn test_replay_redacted_write() {
+    let mut io_wrapper = IOStatWrapper::<_, RecordLog>::new(Cursor::new(Vec::new()), 0);
+    io_wrapper.set_payload_capture(PayloadCapture::Full);
+    io_wrapper.set_payload_redactor(|_action, payload| payload.fill(b'*'));
+    io_wrapper.write_all(b"hunter2").unwrap();
+    assert!(io_wrapper.iop_log()[0].is_payload_redacted());
+
+    // Only the length of a redacted write is checked against the recording
+    let mut replay_io = ReplayIo::new(io_wrapper.iop_log().clone());
+    replay_io.write_all(b"letmein").unwrap();
+    assert!(replay_io.is_finished());
+}
+
+#[test]
+fn test_replay_prefix_write() {
+    let mut io_wrapper = IOStatWrapper::<_, RecordLog>::new(Cursor::new(Vec::new()), 0);
+    io_wrapper.set_payload_capture(PayloadCapture::Prefix(4));
+    io_wrapper.write_all(b"GET /index.html").unwrap();
+    io_wrapper.write_all(b"\r\n").unwrap();
+    assert!(io_wrapper.iop_log()[0].is_payload_truncated());
+    assert!(!io_wrapper.iop_log()[1].is_payload_truncated());
+
+    let mut replay_io = ReplayIo::new(io_wrapper.iop_log().clone());
+    replay_io.write_all(b"GET /about.html").unwrap();
+    replay_io.write_all(b"\r\n").unwrap();
+    assert!(replay_io.is_finished());
+}
+
+#[test]
+#[should_panic(expected = "replay diverged at operation 0: expected write of [71, 69, 84, 32], got [80, 85, 84, 32]")]
+fn test_replay_prefix_write_divergence_panics() {
+    let mut io_wrapper = IOStatWrapper::<_, RecordLog>::new(Cursor::new(Vec::new()), 0);
+    io_wrapper.set_payload_capture(PayloadCapture::Prefix(4));
+    io_wrapper.write_all(b"GET /index.html").unwrap();
+
+    let mut replay_io = ReplayIo::new(io_wrapper.iop_log().clone());
+    replay_io.write_all(b"PUT /index.html").unwrap();
+}
+
+#[test]
+fn test_replay_hashed_write() {
+    let mut io_wrapper = IOStatWrapper::<_, RecordLog>::new(Cursor::new(Vec::new()), 0);
+    io_wrapper.set_payload_capture(PayloadCapture::Hashed);
+    io_wrapper.write_all(b"abc").unwrap();
+    io_wrapper.set_payload_redactor(|_action, payload| payload.fill(b'*'));
+    io_wrapper.write_all(b"secret").unwrap();
+
+    let mut replay_io = ReplayIo::new(io_wrapper.iop_log().clone());
+    replay_io.write_all(b"abc").unwrap();
+    replay_io.write_all(b"hidden").unwrap();
+    assert!(replay_io.is_finished());
+}
+
+#[test]
+#[should_panic(expected = "replay diverged at operation 0: expected write hashing to 0xe71fa2190541574b, got [97, 98, 100] hashing to")]
+fn test_replay_hashed_write_divergence_panics() {
+    let mut io_wrapper = IOStatWrapper::<_, RecordLog>::new(Cursor::new(Vec::new()), 0);
+    io_wrapper.set_payload_capture(PayloadCapture::Hashed);
+    io_wrapper.write_all(b"abc").unwrap();
+
+    let mut replay_io = ReplayIo::new(io_wrapper.iop_log().clone());
+    replay_io.write_all(b"abd").unwrap();
+}
+
+#[test]
+fn test_redactor_flags_only_changed_payloads() {
+    let mut io_wrapper = IOStatWrapper::<_, RecordLog>::new(Cursor::new(Vec::new()), 0);
+    io_wrapper.set_payload_capture(PayloadCapture::Full);
+    io_wrapper.set_payload_redactor(|_action, payload| {
+        if payload.starts_with(b"password=") {
+            payload[9..].iter_mut().for_each(|byte| *byte = b'*');
+        }
+    });
+    io_wrapper.write_all(b"user=admin").unwrap();
+    io_wrapper.write_all(b"password=hunter2").unwrap();
+    assert!(!io_wrapper.iop_log()[0].is_payload_redacted());
+    assert!(io_wrapper.iop_log()[1].is_payload_redacted());
+
+    // The unredacted write is still compared byte for byte
+    let mut replay_io = ReplayIo::new(io_wrapper.iop_log().clone());
+    replay_io.write_all(b"user=admin").unwrap();
+    replay_io.write_all(b"password=letmein").unwrap();
+    assert!(replay_io.is_finished());
+}
+
+#[test]
+fn test_payload_prefix_hash_and_redaction() {
+    let mut io_wrapper = IOStatWrapper::<_, RecordLog>::new(Cursor::new(Vec::new()), 0);
+    io_wrapper.set_payload_redactor(|_action, payload| {
+        if payload.starts_with(b"password=") {
+            payload[9..].iter_mut().for_each(|byte| *byte = b'*');
+        }
+    });
+    io_wrapper.set_payload_capture(PayloadCapture::Prefix(12));
+    io_wrapper.write_all(b"password=hunter2").unwrap();
+    io_wrapper.set_payload_capture(PayloadCapture::Hashed);
+    io_wrapper.write_all(b"password=hunter2").unwrap();
+    io_wrapper.write_all(b"password=swordfish").unwrap();
+    io_wrapper.clear_payload_redactor();
+    io_wrapper.write_all(b"password=hunter2").unwrap();
+
+    let iop_log = io_wrapper.iop_log();
+    assert_eq!(iop_log[0].payload(), Some(&b"password=***"[..]));
+    assert_eq!(iop_log[0].payload_hash(), None);
+    assert_eq!(iop_log[1].payload(), None);
+    // FNV-1a of b"password=*******", so the secret does not affect the hash
+    assert_eq!(iop_log[1].payload_hash(), Some(0xc311_6d13_c177_c80f));
+    assert_ne!(iop_log[1].payload_hash(), iop_log[2].payload_hash());
+    assert_ne!(iop_log[1].payload_hash(), iop_log[3].payload_hash());
+}
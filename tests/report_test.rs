@@ -0,0 +1,102 @@
+use io_wrapper_statistics::{IOStatWrapper, IopInfoPair, StatsTable};
+
+use std::io::{Read, BufRead, Write, Seek, SeekFrom, Cursor, BufReader};
+
+#[test]
+fn test_request_and_seek_distance_counters() {
+    let mut io_wrapper = IOStatWrapper::<_, Vec<IopInfoPair>>::new(Cursor::new(vec![0u8; 16]), 0);
+    let mut read_buf: [u8; 10] = [0; 10];
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 10);
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 6);
+    assert_eq!(io_wrapper.seek(SeekFrom::Start(4)).unwrap(), 4);
+    assert!(io_wrapper.seek(SeekFrom::Current(-10)).is_err());
+    assert_eq!(io_wrapper.seek(SeekFrom::Current(3)).unwrap(), 7);
+    assert_eq!(io_wrapper.write(&[1; 5]).unwrap(), 5);
+    io_wrapper.flush().unwrap();
+
+    assert_eq!(io_wrapper.read_requested_byte_counter(), 20);
+    assert_eq!(io_wrapper.write_requested_byte_counter(), 5);
+    assert_eq!(io_wrapper.seek_distance_counter(), 12 + 3);
+    let shared_stats = io_wrapper.shared_stats();
+    assert_eq!(shared_stats.snapshot(), io_wrapper.stats_snapshot());
+
+    assert_eq!(io_wrapper.stats_snapshot().to_string(), concat!(
+        "read calls         2\n",
+        "read successes     2\n",
+        "read failures      0\n",
+        "read bytes         16\n",
+        "avg read request   10.0\n",
+        "avg read return    8.0\n",
+        "write calls        1\n",
+        "write successes    1\n",
+        "write failures     0\n",
+        "write bytes        5\n",
+        "avg write request  5.0\n",
+        "avg write return   5.0\n",
+        "seek calls         3\n",
+        "seek successes     2\n",
+        "seek failures      1\n",
+        "seek distance      15\n",
+        "flush calls        1\n",
+        "flush successes    1\n",
+        "flush failures     0\n"
+    ));
+    let reset = io_wrapper.reset_stats();
+    assert_eq!(reset.seek_distance_counter(), 15);
+    assert_eq!(io_wrapper.read_requested_byte_counter(), 0);
+    assert_eq!(shared_stats.seek_distance_counter(), 0);
+}
+
+#[test]
+fn test_stats_table() {
+    let mut raw_file = IOStatWrapper::<_, Vec<IopInfoPair>>::new(Cursor::new(vec![0u8; 100]), 0);
+    let raw_stats = raw_file.shared_stats();
+    let mut buf_file = IOStatWrapper::<_, Vec<IopInfoPair>>::new(
+        BufReader::with_capacity(50, raw_file), 0);
+    let mut read_buf: [u8; 10] = [0; 10];
+    for _ in 0..10 {
+        assert_eq!(buf_file.read(&mut read_buf).unwrap(), 10);
+    }
+
+    let table = StatsTable::new()
+        .with_column("buffered", buf_file.stats_snapshot())
+        .with_column("raw", raw_stats.snapshot());
+    assert_eq!(table.column_count(), 2);
+    let rendered = table.render();
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines.len(), 20);
+    assert_eq!(&lines[..7], [
+        "                   buffered   raw",
+        "read calls               10     2",
+        "read successes           10     2",
+        "read failures             0     0",
+        "read bytes              100   100",
+        "avg read request       10.0  50.0",
+        "avg read return        10.0  50.0"
+    ]);
+    assert_eq!(lines[8], "write successes           0     0");
+    assert_eq!(lines[11], "avg write request         -     -");
+    assert_eq!(rendered, table.to_string());
+}
+
+#[test]
+fn test_report_excludes_bufread_bytes_from_read_return() {
+    let mut io_wrapper = IOStatWrapper::<_, Vec<IopInfoPair>>::new(Cursor::new(vec![0u8; 16]), 0);
+    let mut read_buf: [u8; 4] = [0; 4];
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 4);
+    assert_eq!(io_wrapper.fill_buf().unwrap().len(), 12);
+    io_wrapper.consume(6);
+
+    assert_eq!(io_wrapper.read_byte_counter(), 10);
+    assert_eq!(io_wrapper.read_returned_byte_counter(), 4);
+    let report = io_wrapper.stats_snapshot().to_string();
+    assert!(report.contains("read bytes         10\n"));
+    assert!(report.contains("avg read return    4.0\n"));
+
+    // Widths count characters rather than bytes
+    let table = StatsTable::new().with_column("größe", io_wrapper.stats_snapshot());
+    let rendered = table.render();
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines[0], "                   größe");
+    assert_eq!(lines[4], "read bytes            10");
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "serde")]
+
+use io_wrapper_statistics::{IOStatWrapper, IoStatsSnapshot, IopActions, IopRecord, IopResults, RecordLog, SuccessFailureCounter};
+
+use std::io::{Read, Seek, SeekFrom, Cursor, ErrorKind};
+
+#[test]
+fn test_serde_stable_representation() {
+    let actions = [IopActions::Seek(SeekFrom::End(-4)), IopActions::Read(8), IopActions::Flush];
+    assert_eq!(serde_json::to_string(&actions).unwrap(),
+        r#"[{"Seek":{"End":-4}},{"Read":8},"Flush"]"#);
+    let results = [IopResults::Read(Err(ErrorKind::UnexpectedEof)), IopResults::Seek(Ok(3)),
+        IopResults::Flush(Ok(())), IopResults::Consume];
+    let results_json = serde_json::to_string(&results).unwrap();
+    assert_eq!(results_json,
+        r#"[{"Read":{"Err":"UnexpectedEof"}},{"Seek":{"Ok":3}},{"Flush":{"Ok":null}},"Consume"]"#);
+    assert_eq!(serde_json::from_str::<Vec<IopResults>>(&results_json).unwrap(), results);
+
+    let mut counter = SuccessFailureCounter::<u64>::default();
+    counter.increment_success();
+    assert_eq!(serde_json::to_string(&counter).unwrap(), r#"{"success_ctr":1,"failure_ctr":0}"#);
+}
+
+#[test]
+fn test_serde_error_kinds_round_trip() {
+    let results = [IopResults::Write(Err(ErrorKind::StorageFull)), IopResults::Read(Err(ErrorKind::IsADirectory)),
+        IopResults::Seek(Err(ErrorKind::NotSeekable)), IopResults::Write(Err(ErrorKind::QuotaExceeded)),
+        IopResults::Read(Err(ErrorKind::InvalidFilename))];
+    let results_json = serde_json::to_string(&results).unwrap();
+    assert!(results_json.starts_with(r#"[{"Write":{"Err":"StorageFull"}},{"Read":{"Err":"IsADirectory"}}"#));
+    assert_eq!(serde_json::from_str::<Vec<IopResults>>(&results_json).unwrap(), results);
+}
+
+#[test]
+fn test_serde_round_trip_log_and_snapshot() {
+    let mut io_wrapper = IOStatWrapper::<_, RecordLog>::new(Cursor::new(vec![0u8; 16]), 0);
+    io_wrapper.enable_timing();
+    let mut read_buf: [u8; 8] = [0; 8];
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 8);
+    assert!(io_wrapper.seek(SeekFrom::Current(-20)).is_err());
+
+    let log_json = serde_json::to_string(&io_wrapper.iop_log()[..]).unwrap();
+    let iop_log: Vec<IopRecord> = serde_json::from_str(&log_json).unwrap();
+    assert_eq!(iop_log.len(), 2);
+    for (deserialized, original) in iop_log.iter().zip(io_wrapper.iop_log().iter()) {
+        assert_eq!(deserialized.action(), original.action());
+        assert_eq!(deserialized.result(), original.result());
+        assert_eq!(deserialized.timing(), original.timing());
+    }
+
+    let snapshot = io_wrapper.stats_snapshot();
+    let snapshot_json = serde_json::to_string(&snapshot).unwrap();
+    assert_eq!(serde_json::from_str::<IoStatsSnapshot>(&snapshot_json).unwrap(), snapshot);
+}
@@ -0,0 +1,41 @@
+use io_wrapper_statistics::{IOStatWrapper, NoLog};
+
+use std::io::{Read, BufReader, Cursor};
+use std::sync::mpsc;
+use std::thread;
+
+#[test]
+fn test_shared_stats_across_threads() {
+    let mut io_wrapper = IOStatWrapper::<_, NoLog>::new(Cursor::new(vec![0u8; 1024]), 0);
+    let mut read_buf: [u8; 24] = [0; 24];
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 24);
+
+    // Created after some I/O, so the handle must start from the current values
+    let shared_stats = io_wrapper.shared_stats();
+    assert_eq!(shared_stats.read_byte_counter(), 24);
+
+    let (step_tx, step_rx) = mpsc::channel();
+    let (ack_tx, ack_rx) = mpsc::channel();
+    let reader_thread = thread::spawn(move || {
+        let mut buffered_io = BufReader::with_capacity(100, io_wrapper);
+        let mut read_buf: [u8; 50] = [0; 50];
+        while step_rx.recv().is_ok() {
+            buffered_io.read_exact(&mut read_buf).unwrap();
+            ack_tx.send(()).unwrap();
+        }
+        buffered_io.into_inner()
+    });
+
+    // The first read refills the buffer, and the second is served from it
+    for &expected_bytes in &[124, 124, 224] {
+        step_tx.send(()).unwrap();
+        ack_rx.recv().unwrap();
+        assert_eq!(shared_stats.read_byte_counter(), expected_bytes);
+        assert_eq!(shared_stats.seek_pos(), expected_bytes as u64);
+    }
+    drop(step_tx);
+
+    let io_wrapper = reader_thread.join().unwrap();
+    assert_eq!(shared_stats.read_call_counter(), *io_wrapper.read_call_counter());
+    assert_eq!(shared_stats.read_call_counter().success_ctr(), 3);
+}
@@ -0,0 +1,73 @@
+use io_wrapper_statistics::{IOStatWrapper, IoStatsSnapshot, NoLog};
+
+use std::io::{Read, Write, Seek, SeekFrom, Cursor};
+use std::time::Duration;
+
+#[test]
+fn test_snapshot_phase_delta() {
+    let mut io_wrapper = IOStatWrapper::<_, NoLog>::new(Cursor::new(vec![0u8; 100]), 0);
+    let mut read_buf: [u8; 10] = [0; 10];
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 10);
+
+    let before = io_wrapper.stats_snapshot();
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 10);
+    assert_eq!(io_wrapper.write(&[1, 2, 3]).unwrap(), 3);
+    assert_eq!(io_wrapper.seek(SeekFrom::Start(50)).unwrap(), 50);
+    let after = io_wrapper.stats_snapshot();
+
+    let delta = after - before;
+    assert_eq!(delta.read_call_counter().success_ctr(), 1);
+    assert_eq!(delta.read_byte_counter(), 10);
+    assert_eq!(delta.write_call_counter().success_ctr(), 1);
+    assert_eq!(delta.write_byte_counter(), 3);
+    assert_eq!(delta.seek_call_counter().success_ctr(), 1);
+    assert_eq!(delta.seek_pos(), 50);
+    assert_eq!(delta + before, after);
+}
+
+#[test]
+fn test_snapshot_reversed_delta_saturates() {
+    let mut io_wrapper = IOStatWrapper::<_, NoLog>::new(Cursor::new(vec![0u8; 100]), 0);
+    io_wrapper.enable_timing();
+    let before = io_wrapper.stats_snapshot();
+    let mut read_buf: [u8; 10] = [0; 10];
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 10);
+    assert!(io_wrapper.seek(SeekFrom::Current(-20)).is_err());
+    io_wrapper.flush().unwrap();
+    let after = io_wrapper.stats_snapshot();
+
+    let delta = before - after;
+    assert_eq!(delta.read_call_counter().attempt_ctr(), 0);
+    assert_eq!(delta.read_byte_counter(), 0);
+    assert_eq!(delta.read_requested_byte_counter(), 0);
+    assert_eq!(delta.seek_call_counter().failure_ctr(), 0);
+    assert_eq!(delta.write_flush_counter().success_ctr(), 0);
+    assert_eq!(delta.read_time(), Duration::ZERO);
+    assert_eq!(delta.seek_pos(), 0);
+    assert_eq!(delta, IoStatsSnapshot::default());
+}
+
+#[test]
+fn test_snapshot_aggregate_and_reset() {
+    let mut io_wrappers: Vec<_> = (1..=3)
+        .map(|len| IOStatWrapper::<_, NoLog>::new(Cursor::new(vec![0u8; len]), 0))
+        .collect();
+    let mut read_buf: [u8; 8] = [0; 8];
+    for (index, io_wrapper) in io_wrappers.iter_mut().enumerate() {
+        assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), index + 1);
+    }
+    let total: IoStatsSnapshot = io_wrappers.iter()
+        .map(|io_wrapper| io_wrapper.stats_snapshot())
+        .sum();
+    assert_eq!(total.read_call_counter().success_ctr(), 3);
+    assert_eq!(total.read_byte_counter(), 6);
+
+    let io_wrapper = &mut io_wrappers[2];
+    let shared_stats = io_wrapper.shared_stats();
+    let old_stats = io_wrapper.reset_stats();
+    assert_eq!(old_stats.read_byte_counter(), 3);
+    assert_eq!(io_wrapper.read_byte_counter(), 0);
+    assert_eq!(io_wrapper.read_call_counter().attempt_ctr(), 0);
+    assert_eq!(io_wrapper.seek_pos(), 3);
+    assert_eq!(shared_stats.snapshot(), io_wrapper.stats_snapshot());
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "tokio")]
+
+use io_wrapper_statistics::{IOStatWrapper, IopInfoPair, IopKind};
+
+use std::io::{Cursor, SeekFrom};
+use std::io::Result as IOResult;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+/// An I/O object that returns `Poll::Pending` before every operation.
+struct PendingFirst<T> {
+    inner: T,
+    was_pending: bool
+}
+impl<T> PendingFirst<T> {
+    fn new(inner: T) -> PendingFirst<T> {
+        PendingFirst { inner, was_pending: false }
+    }
+    fn poll_pending(&mut self, cx: &mut Context<'_>) -> bool {
+        self.was_pending = !self.was_pending;
+        if self.was_pending {
+            cx.waker().wake_by_ref();
+        }
+        self.was_pending
+    }
+}
+impl<T: AsyncRead + Unpin> AsyncRead for PendingFirst<T> {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<IOResult<()>> {
+        if self.poll_pending(cx) {
+            return Poll::Pending;
+        }
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+impl<T: AsyncWrite + Unpin> AsyncWrite for PendingFirst<T> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<IOResult<usize>> {
+        if self.poll_pending(cx) {
+            return Poll::Pending;
+        }
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IOResult<()>> {
+        if self.poll_pending(cx) {
+            return Poll::Pending;
+        }
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IOResult<()>> {
+        if self.poll_pending(cx) {
+            return Poll::Pending;
+        }
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+#[tokio::test]
+async fn test_tokio_read_write() {
+    let base_io_obj = PendingFirst::new(Cursor::new(vec![0u8; 8]));
+    let mut io_wrapper = IOStatWrapper::<_,Vec<IopInfoPair>>::new(base_io_obj, 0);
+    let mut read_buf: [u8; 4] = [0; 4];
+
+    io_wrapper.read_exact(&mut read_buf).await.unwrap();
+    io_wrapper.write_all(b"abcd").await.unwrap();
+    io_wrapper.flush().await.unwrap();
+    io_wrapper.shutdown().await.unwrap();
+
+    assert_eq!(io_wrapper.read_call_counter().success_ctr(), 1);
+    assert_eq!(io_wrapper.read_byte_counter(), 4);
+    assert_eq!(io_wrapper.write_call_counter().success_ctr(), 1);
+    assert_eq!(io_wrapper.write_byte_counter(), 4);
+    assert_eq!(io_wrapper.write_flush_counter().success_ctr(), 1);
+    assert_eq!(io_wrapper.write_shutdown_counter().success_ctr(), 1);
+    for &kind in &[IopKind::Read, IopKind::Write, IopKind::Flush, IopKind::Shutdown] {
+        assert_eq!(io_wrapper.pending_counter(kind), 1);
+    }
+    assert_eq!(io_wrapper.iop_log().len(), 4);
+    assert_eq!(io_wrapper.into_inner().inner.into_inner(), b"\0\0\0\0abcd");
+}
+
+#[tokio::test]
+async fn test_tokio_seek() {
+    let mut io_wrapper = IOStatWrapper::<_,Vec<IopInfoPair>>::new(Cursor::new(vec![0u8; 8]), 0);
+
+    assert_eq!(io_wrapper.seek(SeekFrom::Start(6)).await.unwrap(), 6);
+    assert_eq!(io_wrapper.stream_position().await.unwrap(), 6);
+    assert!(io_wrapper.seek(SeekFrom::Current(-8)).await.is_err());
+
+    assert_eq!(io_wrapper.seek_call_counter().success_ctr(), 2);
+    assert_eq!(io_wrapper.seek_call_counter().failure_ctr(), 1);
+    assert_eq!(io_wrapper.seek_pos(), 6);
+    assert_eq!(io_wrapper.iop_log().len(), 3);
+}
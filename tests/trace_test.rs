@@ -0,0 +1,61 @@
+use io_wrapper_statistics::{IOStatWrapper, TraceWriter, TraceFormat};
+
+use std::io::{Read, Write, Seek, SeekFrom, Cursor, ErrorKind};
+
+#[derive(Debug)]
+struct FailingSink;
+impl Write for FailingSink {
+    fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+        Err(ErrorKind::BrokenPipe.into())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn run_workload<C: io_wrapper_statistics::IopCollector>(io_wrapper: &mut IOStatWrapper<Cursor<Vec<u8>>, C>) {
+    let mut read_buf: [u8; 8] = [0; 8];
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 8);
+    assert_eq!(io_wrapper.seek(SeekFrom::Start(14)).unwrap(), 14);
+    assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 2);
+    assert!(io_wrapper.seek(SeekFrom::Current(-20)).is_err());
+}
+
+#[test]
+fn test_trace_writer_json_lines() {
+    let trace_writer = TraceWriter::new(Vec::new(), TraceFormat::JsonLines);
+    let mut io_wrapper = IOStatWrapper::with_log(Cursor::new(vec![0u8; 16]), 0, trace_writer);
+    run_workload(&mut io_wrapper);
+    let (_, trace_writer) = io_wrapper.into_parts();
+    assert_eq!(trace_writer.written_count(), 4);
+    let trace = String::from_utf8(trace_writer.finish().unwrap()).unwrap();
+    assert_eq!(trace.lines().collect::<Vec<_>>(), [
+        r#"{"seq":0,"op":"Read","requested":8,"returned":8,"result":"ok","error_kind":null,"pos_before":0,"pos_after":8,"start_ns":null,"elapsed_ns":null}"#,
+        r#"{"seq":1,"op":"Seek","requested":null,"returned":null,"result":"ok","error_kind":null,"pos_before":8,"pos_after":14,"start_ns":null,"elapsed_ns":null}"#,
+        r#"{"seq":2,"op":"Read","requested":8,"returned":2,"result":"ok","error_kind":null,"pos_before":14,"pos_after":16,"start_ns":null,"elapsed_ns":null}"#,
+        r#"{"seq":3,"op":"Seek","requested":null,"returned":null,"result":"err","error_kind":"InvalidInput","pos_before":16,"pos_after":16,"start_ns":null,"elapsed_ns":null}"#
+    ]);
+}
+
+#[test]
+fn test_trace_writer_csv_and_errors() {
+    let trace_writer = TraceWriter::new(Vec::new(), TraceFormat::Csv).with_flush_interval(0);
+    let mut io_wrapper = IOStatWrapper::with_log(Cursor::new(vec![0u8; 16]), 0, trace_writer);
+    io_wrapper.enable_timing();
+    run_workload(&mut io_wrapper);
+    let (_, trace_writer) = io_wrapper.into_parts();
+    let trace = String::from_utf8(trace_writer.finish().unwrap()).unwrap();
+    let lines: Vec<_> = trace.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0], "seq,op,requested,returned,result,error_kind,pos_before,pos_after,start_ns,elapsed_ns");
+    assert!(lines[3].starts_with("2,Read,8,2,ok,,14,16,"));
+    assert!(lines[4].starts_with("3,Seek,,,err,InvalidInput,16,16,"));
+    assert!(lines.iter().skip(1).all(|line| !line.ends_with(',')));
+
+    let mut io_wrapper = IOStatWrapper::with_log(Cursor::new(vec![0u8; 16]), 0,
+        TraceWriter::new(FailingSink, TraceFormat::JsonLines));
+    run_workload(&mut io_wrapper);
+    assert_eq!(io_wrapper.iop_log().written_count(), 0);
+    assert_eq!(io_wrapper.iop_log().error().unwrap().kind(), ErrorKind::BrokenPipe);
+    assert_eq!(io_wrapper.into_parts().1.finish().unwrap_err().kind(), ErrorKind::BrokenPipe);
+}
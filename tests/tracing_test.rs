@@ -0,0 +1,103 @@
+#![cfg(feature = "tracing")]
+
+use io_wrapper_statistics::{IOStatWrapper, TracingLog};
+
+use std::io::{Read, Seek, SeekFrom, Cursor};
+use std::sync::{Arc, Mutex};
+use std::fmt::Debug;
+
+use tracing::{Event, Level, Metadata};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+
+type RecordedEvent = (Level, Vec<(String, String)>);
+
+/// A subscriber that stores every event with its fields formatted as strings.
+struct RecordingSubscriber {
+    events: Arc<Mutex<Vec<RecordedEvent>>>
+}
+struct FieldVisitor<'a>(&'a mut Vec<(String, String)>);
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name().to_owned(), value.to_owned()));
+    }
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push((field.name().to_owned(), format!("{:?}", value)));
+    }
+}
+impl tracing::Subscriber for RecordingSubscriber {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Vec::new();
+        event.record(&mut FieldVisitor(&mut fields));
+        self.events.lock().unwrap().push((*event.metadata().level(), fields));
+    }
+    fn enter(&self, _span: &Id) {}
+    fn exit(&self, _span: &Id) {}
+}
+
+fn field<'a>(event: &'a RecordedEvent, name: &str) -> Option<&'a str> {
+    event.1.iter().find(|(field_name, _)| field_name == name)
+        .map(|(_, value)| value.as_str())
+}
+
+#[test]
+fn test_tracing_log_levels_and_fields() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let subscriber = RecordingSubscriber { events: events.clone() };
+    tracing::subscriber::with_default(subscriber, || {
+        let mut io_wrapper = IOStatWrapper::with_log(Cursor::new(vec![0u8; 8]), 0,
+            TracingLog::new().with_label("cursor"));
+        let mut read_buf: [u8; 16] = [0; 16];
+        assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 8);
+        assert!(io_wrapper.seek(SeekFrom::Current(-20)).is_err());
+    });
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 2);
+
+    assert_eq!(events[0].0, Level::TRACE);
+    assert_eq!(field(&events[0], "message"), Some("I/O operation"));
+    assert_eq!(field(&events[0], "op"), Some("Read"));
+    assert_eq!(field(&events[0], "requested"), Some("16"));
+    assert_eq!(field(&events[0], "returned"), Some("8"));
+    assert_eq!(field(&events[0], "pos_after"), Some("8"));
+    assert_eq!(field(&events[0], "label"), Some("cursor"));
+    assert_eq!(field(&events[0], "error_kind"), None);
+    assert_eq!(field(&events[0], "elapsed_ns"), None);
+
+    assert_eq!(events[1].0, Level::WARN);
+    assert_eq!(field(&events[1], "message"), Some("I/O operation failed"));
+    assert_eq!(field(&events[1], "op"), Some("Seek"));
+    assert_eq!(field(&events[1], "error_kind"), Some("InvalidInput"));
+    assert_eq!(field(&events[1], "injected"), Some("false"));
+    assert_eq!(field(&events[1], "requested"), None);
+}
+
+#[test]
+fn test_tracing_log_sampling() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let subscriber = RecordingSubscriber { events: events.clone() };
+    tracing::subscriber::with_default(subscriber, || {
+        let mut io_wrapper = IOStatWrapper::with_log(Cursor::new(vec![0u8; 8]), 0,
+            TracingLog::new().with_success_level(Level::DEBUG)
+                .with_failure_level(Level::ERROR).with_sample_interval(3));
+        let mut read_buf: [u8; 1] = [0; 1];
+        for _ in 0..7 {
+            assert_eq!(io_wrapper.read(&mut read_buf).unwrap(), 1);
+        }
+        assert!(io_wrapper.seek(SeekFrom::Current(-20)).is_err());
+    });
+    let events = events.lock().unwrap();
+    let levels: Vec<Level> = events.iter().map(|event| event.0).collect();
+    assert_eq!(levels, [Level::DEBUG, Level::DEBUG, Level::DEBUG, Level::ERROR]);
+    let positions: Vec<Option<&str>> = events.iter()
+        .map(|event| field(event, "pos_before")).collect();
+    assert_eq!(positions, [Some("0"), Some("3"), Some("6"), Some("7")]);
+}
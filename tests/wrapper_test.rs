@@ -1,8 +1,10 @@
-use io_wrapper_statistics::{IOStatWrapper, IopInfoPair, SuccessFailureCounter};
+use io_wrapper_statistics::{IOStatWrapper, IopActions, IopInfoPair, IopResults, RecordLog, SuccessFailureCounter};
 
-use std::io::{Read, Seek, Write, SeekFrom, Cursor};
+use std::io::{Read, BufRead, Seek, Write, SeekFrom, BufReader, Cursor, IoSlice, IoSliceMut};
 
 #[test]
+// Short reads and writes are impossible on this Cursor, which the counters below check
+#[allow(clippy::unused_io_amount, clippy::unnecessary_mut_passed)]
 fn test_basic_counts() {
     let mut init_data_buf = [0, 1, 2, 3, 4, 5, 6, 7];
     let base_io_obj: Cursor<&mut [u8]> = Cursor::new(&mut init_data_buf[..]);
@@ -24,4 +26,103 @@ fn test_basic_counts() {
     assert_eq!(io_wrapper.seek_pos(), io_wrapper.stream_position().unwrap());
     assert_eq!(io_wrapper.write_call_counter(), &io_count_expect);
     assert_eq!(io_wrapper.write_byte_counter(), 4);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_read_provided_methods_counted() {
+    // Cursor overrides read_exact, read_to_end, and read_vectored
+    let init_data_buf: Vec<u8> = (0..64).collect();
+    let mut io_wrapper = IOStatWrapper::<_,Vec<IopInfoPair>>::new(Cursor::new(init_data_buf), 0);
+
+    let mut exact_buf: [u8; 4] = [0; 4];
+    io_wrapper.read_exact(&mut exact_buf).unwrap();
+    assert_eq!(exact_buf, [0, 1, 2, 3]);
+
+    let mut first_buf: [u8; 2] = [0; 2];
+    let mut second_buf: [u8; 6] = [0; 6];
+    let mut slices = [IoSliceMut::new(&mut first_buf), IoSliceMut::new(&mut second_buf)];
+    assert_eq!(io_wrapper.read_vectored(&mut slices).unwrap(), 8);
+
+    let mut rest_buf = Vec::new();
+    assert_eq!(io_wrapper.read_to_end(&mut rest_buf).unwrap(), 52);
+
+    assert_eq!(io_wrapper.read_byte_counter(), 64);
+    assert_eq!(io_wrapper.seek_pos(), 64);
+    assert_eq!(io_wrapper.seek_pos(), io_wrapper.stream_position().unwrap());
+    assert!(io_wrapper.read_call_counter().success_ctr() >= 3);
+    assert_eq!(io_wrapper.read_call_counter().success_ctr(),
+        io_wrapper.iop_log().len() as u64);
+    assert!(matches!(io_wrapper.iop_log()[1],
+        (IopActions::ReadVectored(2, 8), IopResults::ReadVectored(Ok(8)))));
+}
+
+#[test]
+fn test_write_provided_methods_counted() {
+    let mut io_wrapper = IOStatWrapper::<_,Vec<IopInfoPair>>::new(Cursor::new(Vec::new()), 0);
+
+    io_wrapper.write_all(b"abcd").unwrap();
+    let slices = [IoSlice::new(b"ef"), IoSlice::new(b"ghij")];
+    assert_eq!(io_wrapper.write_vectored(&slices).unwrap(), 6);
+    write!(io_wrapper, "{}-{}", 12, 34).unwrap();
+    io_wrapper.flush().unwrap();
+
+    assert_eq!(io_wrapper.write_byte_counter(), 15);
+    assert_eq!(io_wrapper.seek_pos(), 15);
+    assert_eq!(io_wrapper.seek_pos(), io_wrapper.stream_position().unwrap());
+    assert_eq!(io_wrapper.write_flush_counter().success_ctr(), 1);
+    assert_eq!(io_wrapper.write_call_counter().success_ctr() + 1,
+        io_wrapper.iop_log().len() as u64);
+    assert!(matches!(&io_wrapper.iop_log()[1],
+        (IopActions::WriteVectored(buf_lens), IopResults::WriteVectored(Ok(6))) if buf_lens[..] == [2, 4]));
+    assert_eq!(io_wrapper.into_inner().into_inner(), b"abcdefghij12-34");
+}
+
+#[test]
+fn test_bufread_counts() {
+    let base_io_obj = BufReader::with_capacity(8, Cursor::new(b"abcdefghij\nklm\nnop".to_vec()));
+    let mut io_wrapper = IOStatWrapper::<_,Vec<IopInfoPair>>::new(base_io_obj, 0);
+
+    assert_eq!(io_wrapper.fill_buf().unwrap(), b"abcdefgh");
+    io_wrapper.consume(3);
+    assert_eq!(io_wrapper.fill_buf().unwrap(), b"defgh");
+    io_wrapper.consume(5);
+    assert_eq!(io_wrapper.seek_pos(), 8);
+
+    let mut line = String::new();
+    assert_eq!(io_wrapper.read_line(&mut line).unwrap(), 3);
+    assert_eq!(line, "ij\n");
+    let rest: Vec<String> = (&mut io_wrapper).lines().map(Result::unwrap).collect();
+    assert_eq!(rest, ["klm", "nop"]);
+
+    assert_eq!(io_wrapper.fill_buf_call_counter().success_ctr(), 2);
+    assert_eq!(io_wrapper.fill_buf_hit_counter(), 1);
+    assert_eq!(io_wrapper.fill_buf_miss_counter(), 1);
+    // read_line is called once more by lines() to detect EOF
+    assert_eq!(io_wrapper.read_until_call_counter().success_ctr(), 4);
+    assert_eq!(io_wrapper.read_byte_counter(), 18);
+    assert_eq!(io_wrapper.seek_pos(), 18);
+    assert!(matches!(io_wrapper.iop_log()[1],
+        (IopActions::Consume(3), IopResults::Consume)));
+    assert!(matches!(io_wrapper.iop_log()[4],
+        (IopActions::ReadLine, IopResults::ReadLine(Ok(3)))));
+}
+
+#[test]
+fn test_timing() {
+    let mut io_wrapper = IOStatWrapper::<_,RecordLog>::new(Cursor::new(vec![0; 16]), 0);
+    let mut slice_buf: [u8; 4] = [0; 4];
+
+    assert_eq!(io_wrapper.read(&mut slice_buf).unwrap(), 4);
+    assert!(io_wrapper.timing_epoch().is_none());
+    io_wrapper.enable_timing();
+    assert_eq!(io_wrapper.read(&mut slice_buf).unwrap(), 4);
+    io_wrapper.seek(SeekFrom::Start(0)).unwrap();
+
+    let iop_log = io_wrapper.iop_log();
+    assert!(iop_log[0].timing().is_none());
+    let read_timing = iop_log[1].timing().unwrap();
+    let seek_timing = iop_log[2].timing().unwrap();
+    assert!(read_timing.end() <= seek_timing.start());
+    assert_eq!(io_wrapper.read_time(), read_timing.elapsed());
+    assert_eq!(io_wrapper.seek_time(), seek_timing.elapsed());
+}